@@ -1,3 +1,5 @@
+use radio_datetime_utils::radio_datetime_helpers;
+
 /// Returns the binary-encoded value of the given buffer over the given range, or None if the input is invalid.
 ///
 /// # Arguments
@@ -15,6 +17,92 @@ pub fn get_binary_value(bit_buffer: &[Option<bool>], start: usize, stop: usize)
     Some(val)
 }
 
+/// Returns the BCD-encoded value of the given buffer over the given range, or None if the input
+/// is invalid or if any group of four bits exceeds 9.
+///
+/// # Arguments
+/// * `bit_buffer` - buffer containing the bits
+/// * `start` - start bit position (least significant)
+/// * `stop` - stop bit position (most significant), at most 7 bits after `start`
+pub fn get_bcd_value_checked(bit_buffer: &[Option<bool>], start: usize, stop: usize) -> Option<u8> {
+    if stop < start || stop - start > 7 {
+        return None;
+    }
+    let mut val = 0;
+    let mut mult = 1;
+    for group_start in (start..=stop).step_by(4) {
+        let digit = get_binary_value(bit_buffer, group_start, stop.min(group_start + 3))? as u8;
+        if digit > 9 {
+            return None;
+        }
+        val += mult * digit;
+        mult *= 10;
+    }
+    Some(val)
+}
+
+/// Returns the number of days since 1970-01-01 of the given date, or None if the input is invalid.
+///
+/// # Arguments
+/// * `year` - year within the century, which is assumed to be 2000-2099
+/// * `month` - month, 1-12
+/// * `day` - day of the month, 1-31
+pub fn get_days_since_epoch(year: u8, month: u8, day: u8) -> Option<u32> {
+    const DAYS_BEFORE_MONTH: [u32; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+    if year > 99 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let year = year as u32;
+    let leap_day = (year.is_multiple_of(4) && month > 2) as u32;
+    Some(
+        10_957 // 2000-01-01
+            + 365 * year
+            + year.div_ceil(4)
+            + DAYS_BEFORE_MONTH[month as usize - 1]
+            + leap_day
+            + day as u32
+            - 1,
+    )
+}
+
+/// Returns if bits 36 to 57 of the given buffer contain a valid date of which the day of the week
+/// matches the calendar.
+///
+/// # Arguments
+/// * `bit_buffer` - buffer containing the bits
+pub fn is_consistent_date(bit_buffer: &[Option<bool>]) -> bool {
+    const DAYS_IN_MONTH: [u8; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    if let (Some(day), Some(weekday), Some(month), Some(year)) = (
+        radio_datetime_helpers::get_bcd_value(bit_buffer, 36, 41),
+        radio_datetime_helpers::get_bcd_value(bit_buffer, 42, 44),
+        radio_datetime_helpers::get_bcd_value(bit_buffer, 45, 49),
+        radio_datetime_helpers::get_bcd_value(bit_buffer, 50, 57),
+    ) {
+        if let Some(days) = get_days_since_epoch(year, month, day) {
+            let leap_day = (year.is_multiple_of(4) && month == 2) as u8;
+            // 1970-01-01 was a Thursday
+            return day <= DAYS_IN_MONTH[month as usize - 1] + leap_day
+                && weekday as u32 == (days + 3) % 7 + 1;
+        }
+    }
+    false
+}
+
+/// Returns the bit value of the given character from a log file, or None if the character is invalid.
+///
+/// `'0'` and `'1'` are the regular bit values, `'_'` indicates a broken bit or the end-of-minute marker.
+///
+/// # Arguments
+/// * `c` - character to parse
+pub fn parse_log_bit(c: char) -> Option<Option<bool>> {
+    match c {
+        '0' => Some(Some(false)),
+        '1' => Some(Some(true)),
+        '_' => Some(None),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +154,94 @@ mod tests {
         const BINARY_BUFFER: [Option<bool>; 4] = [Some(true), Some(true), None, Some(false)];
         assert_eq!(get_binary_value(&BINARY_BUFFER, 0, 3), None);
     }
+
+    #[test]
+    fn test_get_bcd_value_checked() {
+        const BCD_BUFFER: [Option<bool>; 8] = [
+            Some(true),
+            Some(false),
+            Some(false),
+            Some(true),
+            Some(true),
+            Some(false),
+            Some(true),
+            Some(false),
+        ];
+        assert_eq!(get_bcd_value_checked(&BCD_BUFFER, 0, 7), Some(59));
+        assert_eq!(get_bcd_value_checked(&BCD_BUFFER, 0, 6), Some(59));
+        assert_eq!(get_bcd_value_checked(&BCD_BUFFER, 4, 6), Some(5));
+    }
+
+    #[test]
+    fn test_get_bcd_value_checked_invalid() {
+        // 0x1a
+        const BCD_BUFFER: [Option<bool>; 7] = [
+            Some(false),
+            Some(true),
+            Some(false),
+            Some(true),
+            Some(true),
+            Some(false),
+            Some(false),
+        ];
+        assert_eq!(get_bcd_value_checked(&BCD_BUFFER, 0, 6), None);
+        assert_eq!(get_bcd_value_checked(&BCD_BUFFER, 4, 6), Some(1));
+        assert_eq!(get_bcd_value_checked(&[None, Some(true)], 0, 1), None);
+        assert_eq!(get_bcd_value_checked(&[Some(true); 9], 0, 8), None);
+    }
+
+    #[test]
+    fn test_get_days_since_epoch() {
+        assert_eq!(get_days_since_epoch(0, 1, 1), Some(10_957));
+        assert_eq!(get_days_since_epoch(0, 3, 1), Some(11_017)); // 2000 is a leap year
+        assert_eq!(get_days_since_epoch(22, 10, 22), Some(19_287));
+        assert_eq!(get_days_since_epoch(24, 12, 31), Some(20_088));
+    }
+
+    #[test]
+    fn test_get_days_since_epoch_invalid() {
+        assert_eq!(get_days_since_epoch(100, 1, 1), None);
+        assert_eq!(get_days_since_epoch(22, 13, 1), None);
+        assert_eq!(get_days_since_epoch(22, 0, 1), None);
+        assert_eq!(get_days_since_epoch(22, 1, 0), None);
+    }
+
+    #[test]
+    fn test_is_consistent_date() {
+        let mut bit_buffer = [None; 59];
+        // Saturday 2022-10-22, see BIT_BUFFER in lib.rs
+        for (b, v) in [
+            false, true, false, false, false, true, // day 22
+            false, true, true, // Saturday
+            false, false, false, false, true, // October
+            false, true, false, false, false, true, false, false, // year 22
+        ]
+        .iter()
+        .enumerate()
+        {
+            bit_buffer[36 + b] = Some(*v);
+        }
+        assert_eq!(is_consistent_date(&bit_buffer), true);
+        bit_buffer[42] = Some(true); // Sunday
+        assert_eq!(is_consistent_date(&bit_buffer), false);
+        bit_buffer[36] = Some(true); // 2022-10-23
+        assert_eq!(is_consistent_date(&bit_buffer), true);
+        bit_buffer[40] = Some(true); // 2022-10-33
+        assert_eq!(is_consistent_date(&bit_buffer), false);
+        bit_buffer[40] = None;
+        assert_eq!(is_consistent_date(&bit_buffer), false);
+    }
+
+    #[test]
+    fn test_parse_log_bit() {
+        assert_eq!(parse_log_bit('0'), Some(Some(false)));
+        assert_eq!(parse_log_bit('1'), Some(Some(true)));
+        assert_eq!(parse_log_bit('_'), Some(None));
+    }
+
+    #[test]
+    fn test_parse_log_bit_invalid() {
+        assert_eq!(parse_log_bit('2'), None);
+        assert_eq!(parse_log_bit(' '), None);
+    }
 }
@@ -1,8 +1,14 @@
 //! Collection of utilities for DCF77 receivers.
 
 //! Build with no_std for embedded platforms.
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+use core::fmt;
 use radio_datetime_utils::{radio_datetime_helpers, RadioDateTimeUtils};
 
 pub mod dcf77_helpers;
@@ -17,12 +23,168 @@ const ACTIVE_RUNAWAY: u32 = 250_000;
 const MINUTE_LIMIT: u32 = 1_500_000;
 /// Signal is considered lost after this many microseconds
 const PASSIVE_RUNAWAY: u32 = 2_500_000;
-
+/// Target rate of spikes passing the adaptive spike limit, in 1/256 spikes per second
+const SPIKE_RATE_TARGET: u32 = 26;
+/// Step in microseconds by which the adaptive spike limit changes per second
+const SPIKE_LIMIT_STEP: u32 = 1_000;
+/// Upper limit in microseconds for the adaptive spike limit, well below the active part of a 0 bit
+const ADAPTIVE_SPIKE_LIMIT_MAX: u32 = 65_000;
+/// Number of minutes for which the transmitter call bit is remembered
+pub const CALL_BIT_HISTORY_SIZE: usize = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DecodeType {
     Live,
     LogFile,
 }
 
+/// Input for `DCF77Utils::step()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepInput {
+    /// New edge for Live mode, see `DCF77Utils::handle_new_edge()`
+    Edge(bool, u32),
+    /// Value of the current bit for LogFile mode, see `DCF77Utils::set_current_bit()`
+    Bit(Option<bool>),
+}
+
+/// Result of `DCF77Utils::step()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StepResult {
+    /// A minute was completed and decoded
+    pub minute_completed: bool,
+    /// The second counter after this step
+    pub second: u8,
+}
+
+/// Day of the week, as broadcast in bits 42 to 44
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Date field which most likely contains the error when the date parity is bad
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateParityHint {
+    /// Bits 36 to 41
+    Day,
+    /// Bits 42 to 44
+    Weekday,
+    /// Bits 45 to 49
+    Month,
+    /// Bits 50 to 57
+    Year,
+    /// Bit 58, the date parity bit itself
+    Parity,
+    /// The error could not be attributed to a single field
+    Unknown,
+}
+
+/// Outcome of the last call to `DCF77Utils::decode_time()`, the first failed check is reported
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeOutcome {
+    /// The minute did not have the expected length
+    IncompleteMinute,
+    /// Bad parity over bits 21 to 28
+    ParityMinute,
+    /// Bad parity over bits 29 to 35
+    ParityHour,
+    /// Bad parity over bits 36 to 58
+    ParityDate,
+    /// Bit 0 is not 0
+    Bit0Wrong,
+    /// Bit 20 is not 1
+    Bit20Wrong,
+    /// The DST bits 17 and 18 are not consistent
+    DstInvalid,
+    /// All checks passed, but a field contains an impossible value, e.g. a BCD digit above 9
+    InvalidValue,
+    /// All checks passed, but the minute was shorter than expected,
+    /// see `DCF77Utils::set_decode_partial_minutes()`
+    Partial,
+    /// All checks passed
+    Ok,
+}
+
+/// Leap second state of the current minute
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeapState {
+    /// No leap second announced or processed
+    NotAnnounced,
+    /// Leap second announced for the end of this hour
+    Announced,
+    /// Leap second processed in this minute
+    Processed,
+}
+
+/// Daylight saving time state of the current minute
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DstState {
+    /// Winter time (CET)
+    Winter,
+    /// Summer time (CEST)
+    Summer,
+    /// Winter time, change to summer time announced for the end of this hour
+    AnnouncedToSummer,
+    /// Summer time, change to winter time announced for the end of this hour
+    AnnouncedToWinter,
+}
+
+/// Decoded date and time, displayed like `2022-10-22 16:58 CEST Sat` with dashes for unknown values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodedTime {
+    pub year: Option<u8>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub weekday: Option<Weekday>,
+    pub dst: Option<DstState>,
+}
+
+/// Write the given value with two digits, or `--` if it is unknown.
+fn write_two_digits(f: &mut fmt::Formatter, value: Option<u8>) -> fmt::Result {
+    match value {
+        Some(v) => write!(f, "{:02}", v),
+        None => f.write_str("--"),
+    }
+}
+
+impl fmt::Display for DecodedTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.year.is_some() { "20" } else { "--" })?;
+        write_two_digits(f, self.year)?;
+        f.write_str("-")?;
+        write_two_digits(f, self.month)?;
+        f.write_str("-")?;
+        write_two_digits(f, self.day)?;
+        f.write_str(" ")?;
+        write_two_digits(f, self.hour)?;
+        f.write_str(":")?;
+        write_two_digits(f, self.minute)?;
+        f.write_str(match self.dst {
+            Some(DstState::Summer) | Some(DstState::AnnouncedToWinter) => " CEST ",
+            Some(DstState::Winter) | Some(DstState::AnnouncedToSummer) => " CET ",
+            None => " -- ",
+        })?;
+        f.write_str(match self.weekday {
+            Some(Weekday::Monday) => "Mon",
+            Some(Weekday::Tuesday) => "Tue",
+            Some(Weekday::Wednesday) => "Wed",
+            Some(Weekday::Thursday) => "Thu",
+            Some(Weekday::Friday) => "Fri",
+            Some(Weekday::Saturday) => "Sat",
+            Some(Weekday::Sunday) => "Sun",
+            None => "--",
+        })
+    }
+}
+
 /// DCF77 decoder class
 pub struct DCF77Utils {
     decode_type: DecodeType,
@@ -41,10 +203,124 @@ pub struct DCF77Utils {
     third_party: Option<u16>,
     call_bit: Option<bool>,
     bit_20: Option<bool>,
+    dst_bits_valid: Option<bool>,
+    call_bit_history: [Option<bool>; CALL_BIT_HISTORY_SIZE],
+    call_bit_history_length: usize,
+    uptime_minutes: u32,
+    minutes_since_decode: u32,
+    decode_outcome: DecodeOutcome,
+    desync_detected: bool,
+    minute_marker_seen: bool,
+    wrapped_seconds: u8,
+    observed_minute_length: Option<u8>,
+    minute_after_decode: bool,
+    hold_last_good: bool,
+    decode_partial_minutes: bool,
+    // below for checking the order of method calls
+    validate_order: bool,
+    after_increase_second: bool,
+    order_violation: bool,
     // below for handle_new_edge()
     before_first_edge: bool,
     t0: u32,
     spike_limit: u32,
+    adaptive_spike_limit: bool,
+    short_intervals: u8,
+    rejected_spikes: u8,
+    passed_spike_rate: u32,
+    rejected_spike_rate: u32,
+    last_edge_rejected: bool,
+    signal_lost: bool,
+    t_second: Option<u32>,
+    second_phase_error: Option<i32>,
+    // below for handle_sample()
+    sample_active: Option<bool>,
+    t_sample_change: Option<u32>,
+}
+
+/// Copy of the decoder state, suitable for sending to another host.
+///
+/// With the `serde` feature enabled, this structure can be serialized and deserialized
+/// without requiring `std` or `alloc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DCF77Snapshot {
+    pub first_minute: bool,
+    pub second: u8,
+    pub old_second: u8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_bit_buffer"))]
+    pub bit_buffer: [Option<bool>; radio_datetime_utils::BIT_BUFFER_SIZE],
+    pub minute: Option<u8>,
+    pub hour: Option<u8>,
+    pub weekday: Option<u8>,
+    pub day: Option<u8>,
+    pub month: Option<u8>,
+    pub year: Option<u8>,
+    pub dst: Option<u8>,
+    pub leap_second: Option<u8>,
+    pub parity_1: Option<bool>,
+    pub parity_2: Option<bool>,
+    pub parity_3: Option<bool>,
+    pub leap_second_is_one: Option<bool>,
+    pub bit_0: Option<bool>,
+    pub third_party: Option<u16>,
+    pub call_bit: Option<bool>,
+    pub bit_20: Option<bool>,
+    pub dst_bits_valid: Option<bool>,
+    pub desync_detected: bool,
+    pub jump_minute: bool,
+    pub jump_hour: bool,
+    pub jump_weekday: bool,
+    pub jump_day: bool,
+    pub jump_month: bool,
+    pub jump_year: bool,
+}
+
+/// serde only supports arrays up to 32 elements, so (de)serialize the bit buffer as a tuple.
+#[cfg(feature = "serde")]
+mod serde_bit_buffer {
+    use core::fmt;
+    use serde::de::{Deserializer, Error, SeqAccess, Visitor};
+    use serde::ser::{SerializeTuple, Serializer};
+
+    const LEN: usize = radio_datetime_utils::BIT_BUFFER_SIZE;
+
+    pub fn serialize<S: Serializer>(
+        bit_buffer: &[Option<bool>; LEN],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(LEN)?;
+        for b in bit_buffer {
+            tuple.serialize_element(b)?;
+        }
+        tuple.end()
+    }
+
+    struct BitBufferVisitor;
+
+    impl<'de> Visitor<'de> for BitBufferVisitor {
+        type Value = [Option<bool>; LEN];
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an array of {} optional bits", LEN)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bit_buffer = [None; LEN];
+            for (i, b) in bit_buffer.iter_mut().enumerate() {
+                *b = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+            }
+            Ok(bit_buffer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[Option<bool>; LEN], D::Error> {
+        deserializer.deserialize_tuple(LEN, BitBufferVisitor)
+    }
 }
 
 /// Abstract generic version of get_*_minute_length()
@@ -87,10 +363,90 @@ impl DCF77Utils {
             third_party: None,
             call_bit: None,
             bit_20: None,
+            dst_bits_valid: None,
+            call_bit_history: [None; CALL_BIT_HISTORY_SIZE],
+            call_bit_history_length: 0,
+            uptime_minutes: 0,
+            minutes_since_decode: 0,
+            decode_outcome: DecodeOutcome::IncompleteMinute,
+            desync_detected: false,
+            minute_marker_seen: false,
+            wrapped_seconds: 0,
+            observed_minute_length: None,
+            minute_after_decode: false,
+            hold_last_good: false,
+            decode_partial_minutes: false,
+            validate_order: false,
+            after_increase_second: false,
+            order_violation: false,
             before_first_edge: true,
             t0: 0,
             spike_limit: SPIKE_LIMIT,
+            adaptive_spike_limit: false,
+            short_intervals: 0,
+            rejected_spikes: 0,
+            passed_spike_rate: 0,
+            rejected_spike_rate: 0,
+            last_edge_rejected: false,
+            signal_lost: false,
+            t_second: None,
+            second_phase_error: None,
+            sample_active: None,
+            t_sample_change: None,
+        }
+    }
+
+    /// Reset this instance to the state right after `new()`, for example to recover from a
+    /// prolonged loss of signal.
+    ///
+    /// User-configured values like the decode type, the (adaptive) spike limit, holding the last
+    /// good date/time, decoding partial minutes, and order validation are preserved.
+    ///
+    /// The date/time structure, the uptime, and the call bit history are _not_ cleared unless
+    /// `reset_datetime` is set, so their history (e.g. the number of minutes running) is kept
+    /// by default.
+    ///
+    /// # Arguments
+    /// * `reset_datetime` - also reset the date/time structure to its initial state
+    pub fn reset(&mut self, reset_datetime: bool) {
+        self.first_minute = true;
+        self.new_minute = false;
+        self.new_second = false;
+        self.second = 0;
+        self.old_second = 0;
+        self.bit_buffer = [None; radio_datetime_utils::BIT_BUFFER_SIZE];
+        if reset_datetime {
+            self.radio_datetime = RadioDateTimeUtils::new(7);
+            self.uptime_minutes = 0;
+            self.call_bit_history = [None; CALL_BIT_HISTORY_SIZE];
+            self.call_bit_history_length = 0;
         }
+        self.leap_second_is_one = None;
+        self.parity_1 = None;
+        self.parity_2 = None;
+        self.parity_3 = None;
+        self.bit_0 = None;
+        self.third_party = None;
+        self.call_bit = None;
+        self.bit_20 = None;
+        self.dst_bits_valid = None;
+        self.minutes_since_decode = 0;
+        self.decode_outcome = DecodeOutcome::IncompleteMinute;
+        self.desync_detected = false;
+        self.minute_marker_seen = false;
+        self.wrapped_seconds = 0;
+        self.observed_minute_length = None;
+        self.minute_after_decode = false;
+        self.after_increase_second = false;
+        self.order_violation = false;
+        self.before_first_edge = true;
+        self.t0 = 0;
+        self.last_edge_rejected = false;
+        self.signal_lost = false;
+        self.t_second = None;
+        self.second_phase_error = None;
+        self.sample_active = None;
+        self.t_sample_change = None;
     }
 
     /// Return if this is before the first minute that has been successfully decoded.
@@ -110,6 +466,7 @@ impl DCF77Utils {
     /// This method must be called _before_ `increase_second()`
     pub fn force_new_minute(&mut self) {
         self.new_minute = true;
+        self.after_increase_second = false;
     }
 
     /// Return if a new second has arrived.
@@ -132,6 +489,194 @@ impl DCF77Utils {
         self.radio_datetime
     }
 
+    /// Get the decoded date and time, which implements `Display`.
+    pub fn get_decoded_time(&self) -> DecodedTime {
+        DecodedTime {
+            year: self.radio_datetime.get_year(),
+            month: self.radio_datetime.get_month(),
+            day: self.radio_datetime.get_day(),
+            hour: self.radio_datetime.get_hour(),
+            minute: self.radio_datetime.get_minute(),
+            weekday: self.get_weekday_enum(),
+            dst: self.get_dst_state(),
+        }
+    }
+
+    /// Get a snapshot of the decoder state, for example to send it to another host after each minute.
+    pub fn get_snapshot(&self) -> DCF77Snapshot {
+        DCF77Snapshot {
+            first_minute: self.first_minute,
+            second: self.second,
+            old_second: self.old_second,
+            bit_buffer: self.bit_buffer,
+            minute: self.radio_datetime.get_minute(),
+            hour: self.radio_datetime.get_hour(),
+            weekday: self.radio_datetime.get_weekday(),
+            day: self.radio_datetime.get_day(),
+            month: self.radio_datetime.get_month(),
+            year: self.radio_datetime.get_year(),
+            dst: self.radio_datetime.get_dst(),
+            leap_second: self.radio_datetime.get_leap_second(),
+            parity_1: self.parity_1,
+            parity_2: self.parity_2,
+            parity_3: self.parity_3,
+            leap_second_is_one: self.leap_second_is_one,
+            bit_0: self.bit_0,
+            third_party: self.third_party,
+            call_bit: self.call_bit,
+            bit_20: self.bit_20,
+            dst_bits_valid: self.dst_bits_valid,
+            desync_detected: self.desync_detected,
+            jump_minute: self.radio_datetime.get_jump_minute(),
+            jump_hour: self.radio_datetime.get_jump_hour(),
+            jump_weekday: self.radio_datetime.get_jump_weekday(),
+            jump_day: self.radio_datetime.get_jump_day(),
+            jump_month: self.radio_datetime.get_jump_month(),
+            jump_year: self.radio_datetime.get_jump_year(),
+        }
+    }
+
+    /// Initialize a new DCF77Utils instance from a snapshot, for example to rebuild the decoder
+    /// on another host.
+    ///
+    /// The date/time values are restored, the DST and leap second announcements are taken over
+    /// as if they were received in a single minute. The jump flags cannot be restored, they are
+    /// recomputed when the next minute is decoded.
+    ///
+    /// The DST_PROCESSED and LEAP_PROCESSED flags cannot be restored either, so for the minute
+    /// in which a DST change or leap second was processed, `get_snapshot()` of the restored
+    /// instance differs from `snapshot` in its `dst` and `leap_second` fields.
+    ///
+    /// # Arguments
+    /// * `dt` - the decode type of the new instance
+    /// * `snapshot` - the snapshot to restore, see `get_snapshot()`
+    pub fn from_snapshot(dt: DecodeType, snapshot: &DCF77Snapshot) -> Self {
+        let mut dcf77 = Self::new(dt);
+        dcf77.first_minute = snapshot.first_minute;
+        dcf77.second = snapshot.second;
+        dcf77.old_second = snapshot.old_second;
+        dcf77.bit_buffer = snapshot.bit_buffer;
+        dcf77
+            .radio_datetime
+            .set_minute(snapshot.minute, true, false);
+        dcf77.radio_datetime.set_hour(snapshot.hour, true, false);
+        dcf77
+            .radio_datetime
+            .set_weekday(snapshot.weekday, true, false);
+        dcf77.radio_datetime.set_month(snapshot.month, true, false);
+        dcf77.radio_datetime.set_year(snapshot.year, true, false);
+        dcf77.radio_datetime.set_day(snapshot.day, true, false);
+        if let Some(dst) = snapshot.dst {
+            dcf77.radio_datetime.set_dst(
+                Some((dst & radio_datetime_utils::DST_SUMMER) != 0),
+                Some((dst & radio_datetime_utils::DST_ANNOUNCED) != 0),
+                false,
+            );
+        }
+        if let Some(leap_second) = snapshot.leap_second {
+            dcf77.radio_datetime.set_leap_second(
+                Some((leap_second & radio_datetime_utils::LEAP_ANNOUNCED) != 0),
+                60,
+            );
+        }
+        dcf77.parity_1 = snapshot.parity_1;
+        dcf77.parity_2 = snapshot.parity_2;
+        dcf77.parity_3 = snapshot.parity_3;
+        dcf77.leap_second_is_one = snapshot.leap_second_is_one;
+        dcf77.bit_0 = snapshot.bit_0;
+        dcf77.third_party = snapshot.third_party;
+        dcf77.call_bit = snapshot.call_bit;
+        dcf77.bit_20 = snapshot.bit_20;
+        dcf77.dst_bits_valid = snapshot.dst_bits_valid;
+        dcf77.desync_detected = snapshot.desync_detected;
+        dcf77
+    }
+
+    /// Get the number of minute boundaries observed since construction, regardless of
+    /// the minutes being decoded successfully.
+    pub fn uptime_minutes(&self) -> u32 {
+        self.uptime_minutes
+    }
+
+    /// Get the day of the week, or None if it is unknown.
+    pub fn get_weekday_enum(&self) -> Option<Weekday> {
+        match self.radio_datetime.get_weekday()? {
+            1 => Some(Weekday::Monday),
+            2 => Some(Weekday::Tuesday),
+            3 => Some(Weekday::Wednesday),
+            4 => Some(Weekday::Thursday),
+            5 => Some(Weekday::Friday),
+            6 => Some(Weekday::Saturday),
+            7 => Some(Weekday::Sunday),
+            _ => None,
+        }
+    }
+
+    /// Get the leap second state, or None if it is unknown.
+    pub fn get_leap_state(&self) -> Option<LeapState> {
+        let leap_second = self.radio_datetime.get_leap_second()?;
+        Some(
+            if (leap_second & radio_datetime_utils::LEAP_PROCESSED) != 0 {
+                LeapState::Processed
+            } else if (leap_second & radio_datetime_utils::LEAP_ANNOUNCED) != 0 {
+                LeapState::Announced
+            } else {
+                LeapState::NotAnnounced
+            },
+        )
+    }
+
+    /// Get the daylight saving time state, or None if it is unknown.
+    pub fn get_dst_state(&self) -> Option<DstState> {
+        let dst = self.radio_datetime.get_dst()?;
+        let summer = (dst & radio_datetime_utils::DST_SUMMER) != 0;
+        Some(if (dst & radio_datetime_utils::DST_ANNOUNCED) != 0 {
+            if summer {
+                DstState::AnnouncedToWinter
+            } else {
+                DstState::AnnouncedToSummer
+            }
+        } else if summer {
+            DstState::Summer
+        } else {
+            DstState::Winter
+        })
+    }
+
+    /// Get the number of consecutive minutes for which `decode_time()` did not decode the time
+    /// successfully, 0 means that the last minute was decoded successfully.
+    ///
+    /// Together with `get_first_minute()`, this distinguishes never having been synchronized
+    /// from having stale information.
+    pub fn get_minutes_since_decode(&self) -> u32 {
+        self.minutes_since_decode
+    }
+
+    /// Return the outcome of the checks performed by the last call to `decode_time()`,
+    /// which is IncompleteMinute if it has not been called yet.
+    ///
+    /// The outcome is only Ok (or Partial) if the minute was decoded successfully,
+    /// see `get_minutes_since_decode()`.
+    pub fn last_decode_result(&self) -> DecodeOutcome {
+        self.decode_outcome
+    }
+
+    /// Return if the end-of-minute marker was missed, which resynchronized the second counter.
+    ///
+    /// This flag is cleared at the next regular new minute.
+    pub fn get_desync_detected(&self) -> bool {
+        self.desync_detected
+    }
+
+    /// Get the number of seconds actually counted between the last two new minutes,
+    /// or None if fewer than two new minutes were seen.
+    ///
+    /// Unlike `get_this_minute_length()`, this shows anomalous lengths caused by missed or
+    /// extra edges, including overflows of the second counter.
+    pub fn get_observed_minute_length(&self) -> Option<u8> {
+        self.observed_minute_length
+    }
+
     /// Get the leap-second-is-one anomaly.
     pub fn get_leap_second_is_one(&self) -> Option<bool> {
         self.leap_second_is_one
@@ -152,6 +697,76 @@ impl DCF77Utils {
         self.parity_3
     }
 
+    /// Get the minute, hour, and date parity bits in that order, Some(false) means OK.
+    pub fn get_parities(&self) -> [Option<bool>; 3] {
+        [self.parity_1, self.parity_2, self.parity_3]
+    }
+
+    /// Get the date field which most likely contains the error if the date parity is bad,
+    /// or None if the date parity is not bad.
+    ///
+    /// This is a best-effort guess, meant for highlighting. It looks for single-bit flips
+    /// in bits 36 to 58 which restore the expected date, or otherwise which result in a valid
+    /// date of which the day of the week matches the calendar.
+    pub fn get_date_parity_hint(&self) -> Option<DateParityHint> {
+        if self.parity_3 != Some(true) {
+            return None;
+        }
+        let expected = [
+            self.radio_datetime.get_day(),
+            self.radio_datetime.get_weekday(),
+            self.radio_datetime.get_month(),
+            self.radio_datetime.get_year(),
+        ];
+        if expected.iter().all(|v| v.is_some()) {
+            if let Some(field) = self.get_date_flip_field(|bits| {
+                [
+                    radio_datetime_helpers::get_bcd_value(bits, 36, 41),
+                    radio_datetime_helpers::get_bcd_value(bits, 42, 44),
+                    radio_datetime_helpers::get_bcd_value(bits, 45, 49),
+                    radio_datetime_helpers::get_bcd_value(bits, 50, 57),
+                ] == expected
+            }) {
+                return Some(field);
+            }
+        }
+        Some(
+            self.get_date_flip_field(dcf77_helpers::is_consistent_date)
+                .unwrap_or(DateParityHint::Unknown),
+        )
+    }
+
+    /// Return the only date field in which single-bit flips of bits 36 to 58 are accepted,
+    /// or None if there is no such field.
+    ///
+    /// # Arguments
+    /// * `accept` - condition which the bit buffer with a flipped bit must meet
+    fn get_date_flip_field(
+        &self,
+        accept: impl Fn(&[Option<bool>]) -> bool,
+    ) -> Option<DateParityHint> {
+        let mut bits = self.bit_buffer;
+        let mut field = None;
+        for b in 36..=58 {
+            bits[b] = bits[b].map(|v| !v);
+            if accept(&bits[..]) {
+                let this_field = match b {
+                    36..=41 => DateParityHint::Day,
+                    42..=44 => DateParityHint::Weekday,
+                    45..=49 => DateParityHint::Month,
+                    50..=57 => DateParityHint::Year,
+                    _ => DateParityHint::Parity,
+                };
+                if field.is_some() && field != Some(this_field) {
+                    return None;
+                }
+                field = Some(this_field);
+            }
+            bits[b] = self.bit_buffer[b];
+        }
+        field
+    }
+
     /// Get the value of the current bit.
     pub fn get_current_bit(&self) -> Option<bool> {
         self.bit_buffer[self.second as usize]
@@ -168,6 +783,91 @@ impl DCF77Utils {
     pub fn set_current_bit(&mut self, value: Option<bool>) {
         self.bit_buffer[self.second as usize] = value;
         self.new_minute = false;
+        self.after_increase_second = false;
+    }
+
+    /// Feed a line from a log file containing one character per second for a whole minute,
+    /// including the end-of-minute marker. Trailing whitespace is ignored.
+    ///
+    /// See `dcf77_helpers::parse_log_bit()` for the valid characters.
+    ///
+    /// Returns false without altering any state if the line is empty, too long,
+    /// or contains an invalid character.
+    ///
+    /// This method is meant for LogFile mode. Afterwards, the last bit of the line is the
+    /// current bit and the arrival of a new minute is forced, so `decode_time()` and then
+    /// `increase_second()` should be called next.
+    ///
+    /// # Arguments
+    /// * `line` - the line to feed
+    pub fn feed_log_line(&mut self, line: &str) -> bool {
+        let line = line.trim_end();
+        if line.is_empty()
+            || line.chars().count()
+                > self.get_next_minute_length().saturating_sub(self.second) as usize
+            || line
+                .chars()
+                .any(|c| dcf77_helpers::parse_log_bit(c).is_none())
+        {
+            return false;
+        }
+        let mut bits = line
+            .chars()
+            .map(|c| dcf77_helpers::parse_log_bit(c).unwrap())
+            .peekable();
+        while let Some(bit) = bits.next() {
+            self.set_current_bit(bit);
+            if bits.peek().is_some() {
+                self.increase_second();
+            } else {
+                self.force_new_minute();
+            }
+        }
+        true
+    }
+
+    /// Set the bits of a whole minute at once, so that `decode_time()` can be called directly
+    /// afterwards.
+    ///
+    /// A slice of 59 bits contains just the data bits 0 to 58 and implies the end-of-minute
+    /// marker, longer slices include the end-of-minute marker. The remainder of the bit buffer is
+    /// cleared and the current second is set to the end-of-minute marker.
+    ///
+    /// Returns Err without altering any state if the length of `bits` is not 59, 60, or 61.
+    ///
+    /// # Arguments
+    /// * `bits` - the bits of the minute to set
+    #[allow(clippy::result_unit_err)]
+    pub fn set_minute_bits(&mut self, bits: &[Option<bool>]) -> Result<(), ()> {
+        if !(59..=61).contains(&bits.len()) {
+            return Err(());
+        }
+        self.bit_buffer = [None; radio_datetime_utils::BIT_BUFFER_SIZE];
+        self.bit_buffer[..bits.len()].copy_from_slice(bits);
+        self.second = (bits.len() as u8 - 1).max(59);
+        self.old_second = self.second;
+        self.new_minute = false;
+        self.after_increase_second = false;
+        Ok(())
+    }
+
+    /// Get the bits of this minute packed as a pair of values and mask.
+    ///
+    /// Bit N of the values is the bit received at second N, bit N of the mask is 1 when that
+    /// bit was received properly.
+    pub fn get_frame_bits(&self) -> (u64, u64) {
+        let mut values = 0;
+        let mut mask = 0;
+        for (b, bit) in self.bit_buffer[0..self.get_this_minute_length() as usize]
+            .iter()
+            .enumerate()
+        {
+            if let Some(value) = bit {
+                values |= (*value as u64) << b;
+                mask |= 1 << b;
+            }
+        }
+        (values, mask)
     }
 
     /// Get the value of bit 0 (must always be 0).
@@ -185,11 +885,129 @@ impl DCF77Utils {
         self.call_bit
     }
 
+    /// Get the transmitter call bit of each of the last `CALL_BIT_HISTORY_SIZE` minutes at most,
+    /// oldest first. Minutes which could not be decoded are recorded as None.
+    pub fn get_call_bit_history(&self) -> &[Option<bool>] {
+        &self.call_bit_history[CALL_BIT_HISTORY_SIZE - self.call_bit_history_length..]
+    }
+
+    /// Return if the transmitter call bit was set in any of the given number of last minutes.
+    ///
+    /// # Arguments
+    /// * `minutes` - the number of minutes to look back, at most `CALL_BIT_HISTORY_SIZE`
+    pub fn get_call_bit_active_recently(&self, minutes: usize) -> bool {
+        let history = self.get_call_bit_history();
+        history[history.len().saturating_sub(minutes)..].contains(&Some(true))
+    }
+
+    /// Add the call bit of the last minute to the history.
+    fn push_call_bit(&mut self, value: Option<bool>) {
+        self.call_bit_history.copy_within(1.., 0);
+        self.call_bit_history[CALL_BIT_HISTORY_SIZE - 1] = value;
+        if self.call_bit_history_length < CALL_BIT_HISTORY_SIZE {
+            self.call_bit_history_length += 1;
+        }
+    }
+
     /// Get the value of bit 20 (must always be 1).
     pub fn get_bit_20(&self) -> Option<bool> {
         self.bit_20
     }
 
+    /// Return if the bit buffer contains the all-ones test pattern instead of a real date and time,
+    /// i.e. all time and date bits (21 to 58, including the parities) are 1.
+    ///
+    /// The value of bit 20 is not taken into account, it is typically 0 for this pattern.
+    pub fn is_test_pattern(&self) -> bool {
+        self.bit_buffer[21..=58].iter().all(|b| *b == Some(true))
+    }
+
+    /// Get if the DST bits 17 and 18 are consistent, i.e. exactly one of them is set.
+    ///
+    /// Returns None if either bit was not received properly.
+    pub fn get_dst_bits_valid(&self) -> Option<bool> {
+        self.dst_bits_valid
+    }
+
+    /// Get the decode type, i.e. if the input is live or from a log file.
+    pub fn get_decode_type(&self) -> DecodeType {
+        self.decode_type
+    }
+
+    /// Set the decode type while keeping the decoded state, e.g. to switch to live input
+    /// after warming up from a log file.
+    ///
+    /// The order of calling `decode_time()` and `increase_second()` differs between the modes,
+    /// so the decode type should only be changed right after a new minute has been processed.
+    ///
+    /// # Arguments
+    /// * `dt` - the new decode type
+    pub fn set_decode_type(&mut self, dt: DecodeType) {
+        self.decode_type = dt;
+    }
+
+    /// Return if the last successfully decoded date/time is kept when a minute fails to decode.
+    pub fn get_hold_last_good(&self) -> bool {
+        self.hold_last_good
+    }
+
+    /// Keep the last successfully decoded date/time, advanced by one minute each minute,
+    /// when a minute fails to decode instead of partially updating it.
+    ///
+    /// `get_minutes_since_decode()` indicates how stale the date/time is.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the hold flag to.
+    pub fn set_hold_last_good(&mut self, value: bool) {
+        self.hold_last_good = value;
+    }
+
+    /// Return if minutes which ended early are decoded.
+    pub fn get_decode_partial_minutes(&self) -> bool {
+        self.decode_partial_minutes
+    }
+
+    /// Let `decode_time()` decode a minute which ended early, e.g. because of an interference
+    /// pulse, as long as all date/time bits (21 to 58) were received during this minute.
+    /// The outcome of such a minute is reported as Partial.
+    ///
+    /// Because bit 58 must have been received, a regular minute can be one second short at most,
+    /// a minute with a leap second two seconds.
+    ///
+    /// A partial minute never clears `first_minute`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the partial minutes flag to.
+    pub fn set_decode_partial_minutes(&mut self, value: bool) {
+        self.decode_partial_minutes = value;
+    }
+
+    /// Return if the order of calling `decode_time()` and `increase_second()` is validated.
+    pub fn get_validate_order(&self) -> bool {
+        self.validate_order
+    }
+
+    /// Enable or disable validating the order of calling `decode_time()` and `increase_second()`
+    /// for the current decode type, and clear any previously detected violation.
+    ///
+    /// A call to `decode_time()` is correct in Live mode if `increase_second()` was called since
+    /// the previous call to `decode_time()`, edges handled in between do not matter.
+    /// In LogFile mode, it is correct if `set_current_bit()` or `force_new_minute()` was called
+    /// after the last call to `increase_second()`.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the validation flag to.
+    pub fn set_validate_order(&mut self, value: bool) {
+        self.validate_order = value;
+        self.order_violation = false;
+    }
+
+    /// Return if `decode_time()` was called in the wrong order relative to `increase_second()`
+    /// since order validation was enabled.
+    pub fn get_order_violation(&self) -> bool {
+        self.order_violation
+    }
+
     /// Return the current spike limit in microseconds.
     pub fn get_spike_limit(&self) -> u32 {
         self.spike_limit
@@ -205,11 +1023,77 @@ impl DCF77Utils {
         }
     }
 
+    /// Return if the spike limit adapts itself to the spikes seen by `handle_new_edge()`.
+    pub fn get_adaptive_spike_limit(&self) -> bool {
+        self.adaptive_spike_limit
+    }
+
+    /// Let the spike limit adapt itself to the spikes seen by `handle_new_edge()`, starting from
+    /// the current spike limit.
+    ///
+    /// A second is expected to contain at most one interval shorter than ACTIVE_LIMIT
+    /// (the active part of a 0 bit), any further ones are spikes which passed the spike limit.
+    /// At each new second, the spike limit is raised by SPIKE_LIMIT_STEP microseconds while
+    /// the running average of the rate of these spikes exceeds SPIKE_RATE_TARGET. It is lowered
+    /// by the same step when the rate of rejected spikes is below that target as well, so
+    /// the spike limit does not stay high once the noise is gone. The spike limit stays within
+    /// [0..ADAPTIVE_SPIKE_LIMIT_MAX], so the active part of a 0 bit is never taken for a spike.
+    ///
+    /// # Arguments
+    /// * `value` - the value to set the adaptive flag to.
+    pub fn set_adaptive_spike_limit(&mut self, value: bool) {
+        self.adaptive_spike_limit = value;
+        self.short_intervals = 0;
+        self.rejected_spikes = 0;
+        self.passed_spike_rate = 0;
+        self.rejected_spike_rate = 0;
+    }
+
+    /// Update the spike rates with the counts of the second which just ended and adapt
+    /// the spike limit to them.
+    fn adapt_spike_limit(&mut self) {
+        let passed = self.short_intervals.saturating_sub(1) as u32;
+        // exponential moving averages with a weight of 1/8 for the last second
+        self.passed_spike_rate = (7 * self.passed_spike_rate + 256 * passed) / 8;
+        self.rejected_spike_rate =
+            (7 * self.rejected_spike_rate + 256 * self.rejected_spikes as u32) / 8;
+        self.short_intervals = 0;
+        self.rejected_spikes = 0;
+        if passed > 0 && self.passed_spike_rate > SPIKE_RATE_TARGET {
+            self.spike_limit = (self.spike_limit + SPIKE_LIMIT_STEP).min(ADAPTIVE_SPIKE_LIMIT_MAX);
+        } else if self.rejected_spike_rate < SPIKE_RATE_TARGET {
+            self.spike_limit = self.spike_limit.saturating_sub(SPIKE_LIMIT_STEP);
+        }
+    }
+
+    /// Return if the last edge was rejected because its time stamp was before the previous one.
+    pub fn get_last_edge_rejected(&self) -> bool {
+        self.last_edge_rejected
+    }
+
+    /// Return if the signal is lost, i.e. the last edge came after a passive runaway and no
+    /// regular second or minute edge has been seen since.
+    pub fn get_signal_lost(&self) -> bool {
+        self.signal_lost
+    }
+
+    /// Return the difference in microseconds between the measured length of the last
+    /// completed second and its nominal length, or None if it could not be measured.
+    ///
+    /// The nominal length is 1_000_000 microseconds, or 2_000_000 microseconds when
+    /// the second includes the end-of-minute marker.
+    pub fn get_second_phase_error(&self) -> Option<i32> {
+        self.second_phase_error
+    }
+
     /// Determine the bit value if a new edge is received. indicates reception errors,
     /// and checks if a new minute has started.
     ///
     /// This function can deal with spikes, which are arbitrarily set to `spike_limit` microseconds.
     ///
+    /// Edges with a time stamp less than `PASSIVE_RUNAWAY` microseconds before the previous one
+    /// are rejected and leave the state untouched, see `get_last_edge_rejected()`.
+    ///
     /// This method must be called _after_ `increase_second()`
     ///
     /// # Arguments
@@ -217,18 +1101,33 @@ impl DCF77Utils {
     ///                   low-to-high).
     /// * `t` - time stamp of the received edge, in microseconds
     pub fn handle_new_edge(&mut self, is_low_edge: bool, t: u32) {
+        self.process_new_edge(is_low_edge, t);
+    }
+
+    /// Implementation of `handle_new_edge()`, returns if this edge started a new second.
+    fn process_new_edge(&mut self, is_low_edge: bool, t: u32) -> bool {
+        self.last_edge_rejected = false;
         if self.before_first_edge {
             self.before_first_edge = false;
             self.t0 = t;
-            return;
+            return false;
         }
         let t_diff = radio_datetime_helpers::time_diff(self.t0, t);
+        if t_diff > u32::MAX - PASSIVE_RUNAWAY {
+            // time stamp went backwards, which shows up as an impossibly long interval
+            self.last_edge_rejected = true;
+            return false;
+        }
         if t_diff < self.spike_limit {
             // Shift t0 to deal with a train of spikes adding up to more than `spike_limit` microseconds.
             self.t0 += t_diff;
-            return; // random positive or negative spike, ignore
+            self.rejected_spikes = self.rejected_spikes.saturating_add(1);
+            return false; // random positive or negative spike, ignore
         }
         self.t0 = t;
+        if t_diff < ACTIVE_LIMIT {
+            self.short_intervals = self.short_intervals.saturating_add(1);
+        }
         if is_low_edge {
             // leave self.new_minute unaltered
             self.new_second = false;
@@ -242,8 +1141,55 @@ impl DCF77Utils {
         } else if t_diff < PASSIVE_RUNAWAY {
             self.new_minute = t_diff > MINUTE_LIMIT;
             self.new_second = t_diff > 1_000_000 - ACTIVE_RUNAWAY;
+            if self.new_second {
+                if let Some(t_second) = self.t_second {
+                    let nominal = if self.new_minute {
+                        2_000_000
+                    } else {
+                        1_000_000
+                    };
+                    self.second_phase_error =
+                        Some(radio_datetime_helpers::time_diff(t_second, t) as i32 - nominal);
+                }
+                self.t_second = Some(t);
+                self.signal_lost = false;
+                if self.adaptive_spike_limit {
+                    self.adapt_spike_limit();
+                }
+            }
+            return self.new_second;
         } else {
             self.bit_buffer[self.second as usize] = None; // broken bit, passive runaway
+            self.t_second = None;
+            self.signal_lost = true;
+        }
+        false
+    }
+
+    /// Determine the bit value from a periodic sample of the signal, as an alternative to
+    /// `handle_new_edge()`.
+    ///
+    /// A change of the signal level is passed on as an edge to `handle_new_edge()` once the new
+    /// level has been stable for `spike_limit` microseconds, using the time stamp of the first
+    /// sample with the new level. Shorter changes are ignored as spikes.
+    ///
+    /// This method must be called _after_ `increase_second()`
+    ///
+    /// # Arguments
+    /// * `is_active` - indicates that the carrier is reduced (the active part of a second).
+    /// * `t` - time stamp of the sample, in microseconds
+    pub fn handle_sample(&mut self, is_active: bool, t: u32) {
+        match self.sample_active {
+            None => self.sample_active = Some(is_active),
+            Some(active) if active == is_active => self.t_sample_change = None,
+            Some(_) => {
+                let t_change = *self.t_sample_change.get_or_insert(t);
+                if radio_datetime_helpers::time_diff(t_change, t) >= self.spike_limit {
+                    self.sample_active = Some(is_active);
+                    self.t_sample_change = None;
+                    self.handle_new_edge(!is_active, t_change);
+                }
+            }
         }
     }
 
@@ -261,17 +1207,179 @@ impl DCF77Utils {
         )
     }
 
+    /// Return if the current second is the inserted leap second, i.e. the extra bit at position 59
+    /// of a minute which is 61 seconds long. The end-of-minute marker follows at position 60.
+    ///
+    /// The length of the minute in reception is given by `get_next_minute_length()`,
+    /// `get_this_minute_length()` only becomes 61 once this minute has been decoded.
+    /// In LogFile mode, the end-of-minute marker of the minute announcing the leap second
+    /// is not the leap second, even though `get_next_minute_length()` already returns 61.
+    pub fn is_leap_second_now(&self) -> bool {
+        self.minute_after_decode && self.get_next_minute_length() == 61 && self.second == 59
+    }
+
     /// Increase or reset `second`.
     ///
     /// Returns if the second counter was increased/wrapped normally (true)
     /// or due to an overflow (false).
     ///
+    /// An overflow means that the end-of-minute marker was missed. In that case,
+    /// a desynchronization is flagged until the next regular new minute. In Live mode,
+    /// `decode_time()` can still decode the minute which just overflowed, in LogFile mode
+    /// the bit buffer is cleared because `decode_time()` was already called.
+    ///
     /// This method must be called _after_ `decode_time()`, `handle_new_edge()`,
     /// `set_current_bit()`, and `force_new_minute()`.
     pub fn increase_second(&mut self) -> bool {
+        if self.new_minute {
+            self.uptime_minutes = self.uptime_minutes.wrapping_add(1);
+            self.desync_detected = false;
+            if self.minute_marker_seen {
+                self.observed_minute_length =
+                    Some(self.wrapped_seconds.saturating_add(self.second + 1));
+            }
+            self.minute_marker_seen = true;
+            self.wrapped_seconds = 0;
+        }
+        self.after_increase_second = true;
         self.old_second = self.second;
         let minute_length = self.get_next_minute_length();
-        RadioDateTimeUtils::increase_second(&mut self.second, self.new_minute, minute_length)
+        let normal =
+            RadioDateTimeUtils::increase_second(&mut self.second, self.new_minute, minute_length);
+        if self.second == 0 {
+            self.minute_after_decode = true;
+        }
+        if !normal {
+            self.desync_detected = true;
+            if matches!(self.decode_type, DecodeType::LogFile) {
+                // start over with a clean frame
+                self.bit_buffer = [None; radio_datetime_utils::BIT_BUFFER_SIZE];
+            }
+            self.wrapped_seconds = self.wrapped_seconds.saturating_add(minute_length);
+        }
+        normal
+    }
+
+    /// Correct the second counter and the bit buffer if bit 0 and bit 20 indicate that the
+    /// last minute slipped by one second, e.g. due to a spurious or a missed edge.
+    ///
+    /// A minute which was one second too long has bit 0 at position 1 and bit 20 at
+    /// position 21, a minute which was one second too short has bit 20 at position 19.
+    /// In the latter case, the value of bit 0 is lost. A minute with bit 20 at position 20 is
+    /// considered to be aligned, even if bit 0 was not received properly.
+    ///
+    /// This method must be called _before_ `decode_time()`, so _before_ `increase_second()`
+    /// in LogFile mode and _after_ `increase_second()` in Live mode.
+    pub fn resync_second_counter(&mut self) {
+        let second = match self.decode_type {
+            DecodeType::Live => &mut self.old_second,
+            DecodeType::LogFile => &mut self.second,
+        };
+        if self.bit_buffer[0] == Some(false) && self.bit_buffer[20] == Some(true) {
+            return; // properly aligned
+        }
+        if self.bit_buffer[1] == Some(false)
+            && self.bit_buffer[20] != Some(true)
+            && self.bit_buffer[21] == Some(true)
+            && *second > 0
+        {
+            // one second too many
+            self.bit_buffer.copy_within(1.., 0);
+            self.bit_buffer[radio_datetime_utils::BIT_BUFFER_SIZE - 1] = None;
+            *second -= 1;
+        } else if self.bit_buffer[19] == Some(true)
+            && self.bit_buffer[20] != Some(true)
+            && (*second as usize) < radio_datetime_utils::BIT_BUFFER_SIZE - 1
+        {
+            // one second too few
+            self.bit_buffer
+                .copy_within(..radio_datetime_utils::BIT_BUFFER_SIZE - 1, 1);
+            self.bit_buffer[0] = None;
+            *second += 1;
+        }
+    }
+
+    /// Process one input in the correct order of method calls for the decode type.
+    ///
+    /// In Live mode, an edge is handled and if it started a new second, the second counter
+    /// is increased and the time is decoded when a new minute arrived or the end-of-minute
+    /// marker was missed. The end-of-minute marker also accounts for the unmodulated last second
+    /// of the minute, which has no edge of its own: calling `increase_second()` only for each
+    /// edge starting a new second would leave the second counter one short at the end of
+    /// the minute, so `decode_time()` would never see a complete minute.
+    ///
+    /// In LogFile mode, the current bit is set and if it is the last bit of the minute
+    /// (the end-of-minute marker), the time is decoded and a new minute is forced before
+    /// the second counter is increased.
+    ///
+    /// Input not matching the decode type is ignored.
+    ///
+    /// # Arguments
+    /// * `input` - the edge or the bit to process
+    /// * `strict_checks` - passed on to `decode_time()`
+    pub fn step(&mut self, input: StepInput, strict_checks: bool) -> StepResult {
+        let mut minute_completed = false;
+        match (&self.decode_type, input) {
+            (DecodeType::Live, StepInput::Edge(is_low_edge, t)) => {
+                if self.process_new_edge(is_low_edge, t) {
+                    self.second += (self.new_minute
+                        && (self.second as usize) < radio_datetime_utils::BIT_BUFFER_SIZE - 1)
+                        as u8;
+                    let normal = self.increase_second();
+                    if self.new_minute || !normal {
+                        self.decode_time(strict_checks);
+                        minute_completed = true;
+                    }
+                }
+            }
+            (DecodeType::LogFile, StepInput::Bit(value)) => {
+                self.set_current_bit(value);
+                if self.second + 1 >= self.get_next_minute_length() {
+                    self.decode_time(strict_checks);
+                    self.force_new_minute();
+                    minute_completed = true;
+                }
+                self.increase_second();
+            }
+            _ => {}
+        }
+        StepResult {
+            minute_completed,
+            second: self.second,
+        }
+    }
+
+    /// Decode a stream of edges in Live mode, yielding the date/time structure for each minute
+    /// which was decoded successfully, see `get_minutes_since_decode()`.
+    ///
+    /// Each edge is processed using `step()`, so the edges are consumed lazily.
+    ///
+    /// # Arguments
+    /// * `edges` - the edges as pairs of `is_low_edge` and the time stamp in microseconds,
+    ///             see `handle_new_edge()`
+    /// * `strict_checks` - passed on to `decode_time()`
+    pub fn decode_edges<'a, I>(
+        &'a mut self,
+        edges: I,
+        strict_checks: bool,
+    ) -> impl Iterator<Item = RadioDateTimeUtils> + 'a
+    where
+        I: IntoIterator<Item = (bool, u32)>,
+        I::IntoIter: 'a,
+    {
+        let mut edges = edges.into_iter();
+        core::iter::from_fn(move || {
+            for (is_low_edge, t) in edges.by_ref() {
+                if self
+                    .step(StepInput::Edge(is_low_edge, t), strict_checks)
+                    .minute_completed
+                    && self.minutes_since_decode == 0
+                {
+                    return Some(self.radio_datetime);
+                }
+            }
+            None
+        })
     }
 
     /// Call add_minute() on `self.radio_datetime` and passes on that result.
@@ -287,24 +1395,46 @@ impl DCF77Utils {
     /// This method must be called _before_ `increase_second()` in LogFile mode
     /// and _after_ `increase_second()` in Live mode.
     ///
+    /// See `set_hold_last_good()` for keeping the last good date/time.
+    ///
+    /// A field containing a BCD digit above 9 is not set, even if its parity is correct.
+    ///
+    /// See `set_decode_partial_minutes()` for decoding minutes which ended early.
+    ///
     /// # Arguments
     /// * `strict_checks` - checks all parities, DST validity, bit 0, and bit 20 when setting
     ///                     date/time and clearing self.first_minute
     pub fn decode_time(&mut self, strict_checks: bool) {
+        let mut decoded = false;
+        if self.validate_order
+            && self.after_increase_second != matches!(self.decode_type, DecodeType::Live)
+        {
+            self.order_violation = true;
+        }
+        self.after_increase_second = false;
+        // in Live mode, the minute in reception already started
+        self.minute_after_decode = matches!(self.decode_type, DecodeType::Live);
         self.radio_datetime.clear_jumps();
         let mut added_minute = false;
         let minute_length = self.get_next_minute_length();
         if !self.first_minute {
             added_minute = self.radio_datetime.add_minute();
         }
-        if 1 + match self.decode_type {
+        let last_good = self.radio_datetime;
+        let length = 1 + match self.decode_type {
             DecodeType::Live => self.old_second,
             DecodeType::LogFile => self.second,
-        } == minute_length
-        {
+        };
+        // bits beyond `length` are left over from the previous minute
+        let partial = self.decode_partial_minutes
+            && length < minute_length
+            && length > 58
+            && self.bit_buffer[21..=58].iter().all(|b| b.is_some());
+        if length == minute_length || partial {
             self.bit_0 = self.bit_buffer[0];
             self.third_party = dcf77_helpers::get_binary_value(&self.bit_buffer, 1, 14);
             self.call_bit = self.bit_buffer[15];
+            self.push_call_bit(self.call_bit);
             self.bit_20 = self.bit_buffer[20];
 
             self.parity_1 =
@@ -314,10 +1444,13 @@ impl DCF77Utils {
             self.parity_3 =
                 radio_datetime_helpers::get_parity(&self.bit_buffer, 36, 57, self.bit_buffer[58]);
 
-            let dst = if self.bit_buffer[17].is_some()
-                && self.bit_buffer[18].is_some()
-                && self.bit_buffer[17] != self.bit_buffer[18]
+            self.dst_bits_valid = if self.bit_buffer[17].is_some() && self.bit_buffer[18].is_some()
             {
+                Some(self.bit_buffer[17] != self.bit_buffer[18])
+            } else {
+                None
+            };
+            let dst = if self.dst_bits_valid == Some(true) {
                 self.bit_buffer[17]
             } else {
                 None
@@ -329,9 +1462,31 @@ impl DCF77Utils {
                 && self.bit_0 == Some(false)
                 && self.bit_20 == Some(true)
                 && dst.is_some();
-
+            self.decode_outcome = if self.parity_1 != Some(false) {
+                DecodeOutcome::ParityMinute
+            } else if self.parity_2 != Some(false) {
+                DecodeOutcome::ParityHour
+            } else if self.parity_3 != Some(false) {
+                DecodeOutcome::ParityDate
+            } else if self.bit_0 != Some(false) {
+                DecodeOutcome::Bit0Wrong
+            } else if self.bit_20 != Some(true) {
+                DecodeOutcome::Bit20Wrong
+            } else if dst.is_none() {
+                DecodeOutcome::DstInvalid
+            } else if partial {
+                DecodeOutcome::Partial
+            } else {
+                DecodeOutcome::Ok
+            };
+            let bcd_ok = [(21, 27), (29, 34), (36, 41), (42, 44), (45, 49), (50, 57)]
+                .iter()
+                .all(|&(start, stop)| {
+                    dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, start, stop).is_some()
+                });
+
             self.radio_datetime.set_minute(
-                radio_datetime_helpers::get_bcd_value(&self.bit_buffer, 21, 27),
+                dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, 21, 27),
                 if strict_checks {
                     strict_ok
                 } else {
@@ -341,7 +1496,7 @@ impl DCF77Utils {
             );
 
             self.radio_datetime.set_hour(
-                radio_datetime_helpers::get_bcd_value(&self.bit_buffer, 29, 34),
+                dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, 29, 34),
                 if strict_checks {
                     strict_ok
                 } else {
@@ -351,7 +1506,7 @@ impl DCF77Utils {
             );
 
             self.radio_datetime.set_weekday(
-                radio_datetime_helpers::get_bcd_value(&self.bit_buffer, 42, 44),
+                dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, 42, 44),
                 if strict_checks {
                     strict_ok
                 } else {
@@ -361,7 +1516,7 @@ impl DCF77Utils {
             );
 
             self.radio_datetime.set_month(
-                radio_datetime_helpers::get_bcd_value(&self.bit_buffer, 45, 49),
+                dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, 45, 49),
                 if strict_checks {
                     strict_ok
                 } else {
@@ -371,7 +1526,7 @@ impl DCF77Utils {
             );
 
             self.radio_datetime.set_year(
-                radio_datetime_helpers::get_bcd_value(&self.bit_buffer, 50, 57),
+                dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, 50, 57),
                 if strict_checks {
                     strict_ok
                 } else {
@@ -381,7 +1536,7 @@ impl DCF77Utils {
             );
 
             self.radio_datetime.set_day(
-                radio_datetime_helpers::get_bcd_value(&self.bit_buffer, 36, 41),
+                dcf77_helpers::get_bcd_value_checked(&self.bit_buffer, 36, 41),
                 if strict_checks {
                     strict_ok
                 } else {
@@ -402,6 +1557,7 @@ impl DCF77Utils {
             let leap_second = self.radio_datetime.get_leap_second();
             if leap_second.is_some()
                 && (leap_second.unwrap() & radio_datetime_utils::LEAP_PROCESSED) != 0
+                && length > 59
             {
                 self.leap_second_is_one = Some(self.bit_buffer[59] == Some(true));
             }
@@ -410,14 +1566,181 @@ impl DCF77Utils {
                 strict_ok
             } else {
                 self.bit_0 == Some(false) && self.bit_20 == Some(true)
-            } && self.radio_datetime.is_valid()
+            } && bcd_ok
+                && self.radio_datetime.is_valid()
             {
                 // allow displaying of information after the first properly decoded minute
-                self.first_minute = false;
+                if !partial {
+                    self.first_minute = false;
+                }
+                decoded = true;
+            } else if matches!(
+                self.decode_outcome,
+                DecodeOutcome::Ok | DecodeOutcome::Partial
+            ) {
+                self.decode_outcome = DecodeOutcome::InvalidValue;
+            }
+            if self.hold_last_good && !decoded && !self.first_minute {
+                self.radio_datetime = last_good;
             }
 
             self.radio_datetime.bump_minutes_running();
+        } else {
+            self.push_call_bit(None);
+            self.decode_outcome = DecodeOutcome::IncompleteMinute;
+        }
+        self.minutes_since_decode = if decoded {
+            0
+        } else {
+            self.minutes_since_decode.saturating_add(1)
+        };
+    }
+
+    /// Return the percentage of bits of this minute which were received properly.
+    #[cfg(feature = "alloc")]
+    fn reception_quality(&self) -> u8 {
+        let minute_length = self.get_this_minute_length() as usize - 1; // EOM not included
+        let received = self.bit_buffer[0..minute_length]
+            .iter()
+            .filter(|b| b.is_some())
+            .count();
+        (100 * received / minute_length) as u8
+    }
+
+    /// Return the start of the decoded minute as the number of seconds since 1970-01-01 00:00 UTC,
+    /// or None before the first properly decoded minute or if any date/time value is unknown.
+    pub fn get_unix_time(&self) -> Option<u64> {
+        if self.first_minute {
+            return None;
+        }
+        let days = dcf77_helpers::get_days_since_epoch(
+            self.radio_datetime.get_year()?,
+            self.radio_datetime.get_month()?,
+            self.radio_datetime.get_day()?,
+        )?;
+        let utc_offset = if (self.radio_datetime.get_dst()? & radio_datetime_utils::DST_SUMMER) != 0
+        {
+            7200
+        } else {
+            3600
+        };
+        Some(
+            days as u64 * 86_400
+                + self.radio_datetime.get_hour()? as u64 * 3600
+                + self.radio_datetime.get_minute()? as u64 * 60
+                - utc_offset,
+        )
+    }
+
+    /// Return the start of the decoded minute as a `SystemTime`, or None before the first properly
+    /// decoded minute or if any date/time value is unknown.
+    #[cfg(feature = "std")]
+    pub fn to_system_time(&self) -> Option<std::time::SystemTime> {
+        Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(self.get_unix_time()?))
+    }
+
+    /// Return the decoded local date and time (CET or CEST) of the start of the minute,
+    /// or None before the first properly decoded minute or if any date/time value is unknown
+    /// or inconsistent.
+    #[cfg(feature = "chrono")]
+    pub fn to_naive_date_time(&self) -> Option<chrono::NaiveDateTime> {
+        use chrono::Datelike;
+        if self.first_minute {
+            return None;
+        }
+        let date = chrono::NaiveDate::from_ymd_opt(
+            2000 + self.radio_datetime.get_year()? as i32,
+            self.radio_datetime.get_month()? as u32,
+            self.radio_datetime.get_day()? as u32,
+        )?;
+        if date.weekday().number_from_monday() != self.radio_datetime.get_weekday()? as u32 {
+            return None;
         }
+        date.and_hms_opt(
+            self.radio_datetime.get_hour()? as u32,
+            self.radio_datetime.get_minute()? as u32,
+            0,
+        )
+    }
+
+    /// Return the start of the decoded minute in UTC, or None before the first properly
+    /// decoded minute or if any date/time value is unknown or inconsistent.
+    ///
+    /// This is `to_naive_date_time()` corrected for the UTC offset of CET or CEST.
+    #[cfg(feature = "chrono")]
+    pub fn to_date_time_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::TimeZone;
+        let local = self.to_naive_date_time()?;
+        let offset = if (self.radio_datetime.get_dst()? & radio_datetime_utils::DST_SUMMER) != 0 {
+            2
+        } else {
+            1
+        };
+        Some(chrono::Utc.from_utc_datetime(&(local - chrono::Duration::hours(offset))))
+    }
+
+    /// Return the decoded date and time as a compact JSON string, for example
+    /// `{"time":"16:58","date":"2022-10-22","dst":"CEST","valid":true,"quality":100}`
+    ///
+    /// Unknown values are rendered as `null`, `quality` is the percentage of bits of this minute
+    /// which were received properly.
+    #[cfg(feature = "alloc")]
+    pub fn to_json(&self) -> String {
+        let time = match (
+            self.radio_datetime.get_hour(),
+            self.radio_datetime.get_minute(),
+        ) {
+            (Some(hour), Some(minute)) => format!("\"{:02}:{:02}\"", hour, minute),
+            _ => String::from("null"),
+        };
+        let date = match (
+            self.radio_datetime.get_year(),
+            self.radio_datetime.get_month(),
+            self.radio_datetime.get_day(),
+        ) {
+            (Some(year), Some(month), Some(day)) => {
+                format!("\"20{:02}-{:02}-{:02}\"", year, month, day)
+            }
+            _ => String::from("null"),
+        };
+        let dst = match self.radio_datetime.get_dst() {
+            Some(s_dst) if (s_dst & radio_datetime_utils::DST_SUMMER) != 0 => "\"CEST\"",
+            Some(_) => "\"CET\"",
+            None => "null",
+        };
+        format!(
+            "{{\"time\":{},\"date\":{},\"dst\":{},\"valid\":{},\"quality\":{}}}",
+            time,
+            date,
+            dst,
+            !self.first_minute && self.radio_datetime.is_valid(),
+            self.reception_quality()
+        )
+    }
+}
+
+impl fmt::Debug for DCF77Utils {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DCF77Utils")
+            .field("decoded_time", &self.get_decoded_time())
+            .field("leap_state", &self.get_leap_state())
+            .field("first_minute", &self.first_minute)
+            .field("second", &self.second)
+            .field("old_second", &self.old_second)
+            .field("parity_1", &self.parity_1)
+            .field("parity_2", &self.parity_2)
+            .field("parity_3", &self.parity_3)
+            .field("bit_0", &self.bit_0)
+            .field("bit_20", &self.bit_20)
+            .field("dst_bits_valid", &self.dst_bits_valid)
+            .field("leap_second_is_one", &self.leap_second_is_one)
+            .field("third_party", &self.third_party)
+            .field("call_bit", &self.call_bit)
+            .field("minutes_since_decode", &self.minutes_since_decode)
+            .field("decode_outcome", &self.decode_outcome)
+            .field("desync_detected", &self.desync_detected)
+            .field("signal_lost", &self.signal_lost)
+            .finish_non_exhaustive()
     }
 }
 
@@ -696,6 +2019,98 @@ mod tests {
         assert_eq!(dcf77.get_bit_20(), Some(true));
     }
     #[test]
+    fn test_decode_time_complete_minute_ok_parities() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        assert_eq!(dcf77.get_parities(), [None; 3]);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_parities(), [Some(false); 3]);
+    }
+    #[test]
+    fn test_decode_time_complete_minute_ok_snapshot() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        let snapshot = dcf77.get_snapshot();
+        assert_eq!(snapshot.first_minute, false);
+        assert_eq!(snapshot.second, 59);
+        assert_eq!(snapshot.bit_buffer, dcf77.bit_buffer);
+        assert_eq!(snapshot.minute, Some(58));
+        assert_eq!(snapshot.hour, Some(16));
+        assert_eq!(snapshot.weekday, Some(6));
+        assert_eq!(snapshot.day, Some(22));
+        assert_eq!(snapshot.month, Some(10));
+        assert_eq!(snapshot.year, Some(22));
+        assert_eq!(snapshot.dst, Some(radio_datetime_utils::DST_SUMMER));
+        assert_eq!(snapshot.leap_second, Some(0));
+        assert_eq!(snapshot.parity_1, Some(false));
+        assert_eq!(snapshot.parity_2, Some(false));
+        assert_eq!(snapshot.parity_3, Some(false));
+        assert_eq!(snapshot.leap_second_is_one, None);
+        assert_eq!(snapshot.bit_0, Some(false));
+        assert_eq!(snapshot.third_party, Some(0x18f2));
+        assert_eq!(snapshot.call_bit, Some(true));
+        assert_eq!(snapshot.bit_20, Some(true));
+        assert_eq!(snapshot.dst_bits_valid, Some(true));
+        assert_eq!(snapshot.desync_detected, false);
+        assert_eq!(snapshot.jump_minute, false);
+        assert_eq!(snapshot.jump_year, false);
+
+        let restored = DCF77Utils::from_snapshot(DecodeType::LogFile, &snapshot);
+        assert_eq!(restored.get_snapshot(), snapshot);
+        assert_eq!(restored.get_decoded_time(), dcf77.get_decoded_time());
+    }
+    #[test]
+    fn test_snapshot_processed_flags_lost() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        let mut snapshot = dcf77.get_snapshot();
+        // pretend a DST change and a leap second were just processed
+        snapshot.dst = Some(radio_datetime_utils::DST_SUMMER | radio_datetime_utils::DST_PROCESSED);
+        snapshot.leap_second = Some(radio_datetime_utils::LEAP_PROCESSED);
+
+        let restored = DCF77Utils::from_snapshot(DecodeType::LogFile, &snapshot).get_snapshot();
+        assert_ne!(restored, snapshot);
+        assert_eq!(restored.dst, Some(radio_datetime_utils::DST_SUMMER));
+        assert_eq!(
+            restored.leap_second.unwrap() & radio_datetime_utils::LEAP_PROCESSED,
+            0
+        );
+        // everything else is restored
+        let mut expected = snapshot;
+        expected.dst = restored.dst;
+        expected.leap_second = restored.leap_second;
+        assert_eq!(restored, expected);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_serde_round_trip() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.bit_buffer[42] = None;
+        dcf77.decode_time(false);
+        let snapshot = dcf77.get_snapshot();
+        let mut buffer = [0; 256];
+        let bytes = postcard::to_slice(&snapshot, &mut buffer).unwrap();
+        let received: DCF77Snapshot = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(received, snapshot);
+        let restored = DCF77Utils::from_snapshot(DecodeType::LogFile, &received);
+        assert_eq!(restored.get_snapshot(), snapshot);
+    }
+    #[test]
     fn test_decode_time_complete_minute_bad_bits() {
         let mut dcf77 = DCF77Utils::new(DecodeType::Live);
         dcf77.old_second = 59;
@@ -826,12 +2241,14 @@ mod tests {
         dcf77.bit_buffer[28] = Some(false);
         // announce a leap second:
         dcf77.bit_buffer[19] = Some(true);
+        assert_eq!(dcf77.get_leap_state(), None);
         dcf77.decode_time(false);
         assert_eq!(dcf77.radio_datetime.get_minute(), Some(59)); // sanity check
         assert_eq!(
             dcf77.radio_datetime.get_leap_second(),
             Some(radio_datetime_utils::LEAP_ANNOUNCED)
         );
+        assert_eq!(dcf77.get_leap_state(), Some(LeapState::Announced));
         assert_eq!(dcf77.second, 59);
         assert_eq!(dcf77.get_this_minute_length(), 60);
         assert_eq!(dcf77.get_next_minute_length(), 61);
@@ -854,6 +2271,7 @@ mod tests {
             dcf77.radio_datetime.get_leap_second(),
             Some(radio_datetime_utils::LEAP_PROCESSED)
         );
+        assert_eq!(dcf77.get_leap_state(), Some(LeapState::Processed));
         assert_eq!(dcf77.second, 60);
         assert_eq!(dcf77.get_this_minute_length(), 61);
         assert_eq!(dcf77.get_next_minute_length(), 60);
@@ -868,6 +2286,7 @@ mod tests {
         dcf77.decode_time(false);
         assert_eq!(dcf77.radio_datetime.get_minute(), Some(1));
         assert_eq!(dcf77.radio_datetime.get_leap_second(), Some(0));
+        assert_eq!(dcf77.get_leap_state(), Some(LeapState::NotAnnounced));
         assert_eq!(dcf77.second, 59); // sanity check
         assert_eq!(dcf77.get_this_minute_length(), 60);
         assert_eq!(dcf77.get_next_minute_length(), 60);
@@ -885,12 +2304,14 @@ mod tests {
         dcf77.bit_buffer[28] = Some(false);
         // announce a DST change:
         dcf77.bit_buffer[16] = Some(true);
+        assert_eq!(dcf77.get_dst_state(), None);
         dcf77.decode_time(false);
         assert_eq!(dcf77.radio_datetime.get_minute(), Some(59));
         assert_eq!(
             dcf77.radio_datetime.get_dst(),
             Some(radio_datetime_utils::DST_ANNOUNCED | radio_datetime_utils::DST_SUMMER)
         );
+        assert_eq!(dcf77.get_dst_state(), Some(DstState::AnnouncedToWinter));
         // next minute and hour:
         dcf77.bit_buffer[21] = Some(false);
         dcf77.bit_buffer[24] = Some(false);
@@ -908,6 +2329,7 @@ mod tests {
             dcf77.radio_datetime.get_dst(),
             Some(radio_datetime_utils::DST_PROCESSED)
         ); // DST flipped off
+        assert_eq!(dcf77.get_dst_state(), Some(DstState::Winter));
     }
 
     // strict checks
@@ -1161,6 +2583,7 @@ mod tests {
             dcf77.radio_datetime.get_dst(),
             Some(radio_datetime_utils::DST_ANNOUNCED)
         );
+        assert_eq!(dcf77.get_dst_state(), Some(DstState::AnnouncedToSummer));
         // next minute and hour:
         dcf77.bit_buffer[21] = Some(false);
         dcf77.bit_buffer[24] = Some(false);
@@ -1178,6 +2601,7 @@ mod tests {
             dcf77.radio_datetime.get_dst(),
             Some(radio_datetime_utils::DST_PROCESSED | radio_datetime_utils::DST_SUMMER)
         ); // DST flipped on
+        assert_eq!(dcf77.get_dst_state(), Some(DstState::Summer));
     }
 
     #[test]
@@ -1203,10 +2627,17 @@ mod tests {
     fn test_increase_second_same_minute_overflow() {
         let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
         dcf77.second = 59;
+        dcf77.bit_buffer[59] = Some(false);
         // leap second value is None
+        assert_eq!(dcf77.get_desync_detected(), false);
         assert_eq!(dcf77.increase_second(), false);
         assert_eq!(dcf77.first_minute, true);
         assert_eq!(dcf77.second, 0);
+        assert_eq!(dcf77.get_desync_detected(), true);
+        assert_eq!(
+            dcf77.bit_buffer,
+            [None; radio_datetime_utils::BIT_BUFFER_SIZE]
+        );
     }
     #[test]
     fn test_increase_second_new_minute_ok() {
@@ -1218,4 +2649,1211 @@ mod tests {
         assert_eq!(dcf77.first_minute, true);
         assert_eq!(dcf77.second, 0);
     }
+
+    #[test]
+    fn test_resync_second_counter_aligned() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.resync_second_counter();
+        assert_eq!(dcf77.second, 59);
+        assert_eq!(dcf77.bit_buffer[0], Some(false));
+        assert_eq!(dcf77.bit_buffer[20], Some(true));
+    }
+    #[test]
+    fn test_resync_second_counter_aligned_bit_0_lost() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.bit_buffer[0] = None;
+        dcf77.bit_buffer[21] = Some(true); // minute 59
+        dcf77.bit_buffer[28] = Some(false); // minute parity
+        let bit_buffer = dcf77.bit_buffer;
+        dcf77.resync_second_counter();
+        assert_eq!(dcf77.second, 59);
+        assert_eq!(dcf77.bit_buffer, bit_buffer);
+    }
+    #[test]
+    fn test_resync_second_counter_too_long() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 60; // spurious second at the start of the minute
+        dcf77.bit_buffer[0] = Some(true);
+        for b in 0..=58 {
+            dcf77.bit_buffer[b + 1] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.resync_second_counter();
+        assert_eq!(dcf77.second, 59);
+        assert_eq!(dcf77.bit_buffer[0], Some(false));
+        assert_eq!(dcf77.bit_buffer[20], Some(true));
+        assert_eq!(dcf77.bit_buffer[60], None);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.first_minute, false);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+    }
+    #[test]
+    fn test_resync_second_counter_too_short() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.old_second = 58; // bit 0 was missed
+        for b in 1..=58 {
+            dcf77.bit_buffer[b - 1] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.resync_second_counter();
+        assert_eq!(dcf77.old_second, 59);
+        assert_eq!(dcf77.bit_buffer[0], None);
+        assert_eq!(dcf77.bit_buffer[20], Some(true));
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.first_minute, true); // bit 0 is unknown
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.set_spike_limit(20_000);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.first_minute, false);
+        dcf77.handle_new_edge(true, 1_000_000);
+        assert_eq!(dcf77.before_first_edge, false);
+        dcf77.reset(false);
+        assert_eq!(dcf77.first_minute, true);
+        assert_eq!(dcf77.second, 0);
+        assert_eq!(dcf77.old_second, 0);
+        assert_eq!(
+            dcf77.bit_buffer,
+            [None; radio_datetime_utils::BIT_BUFFER_SIZE]
+        );
+        assert_eq!(dcf77.get_parities(), [None; 3]);
+        assert_eq!(dcf77.leap_second_is_one, None);
+        assert_eq!(dcf77.get_bit_0(), None);
+        assert_eq!(dcf77.get_third_party_buffer(), None);
+        assert_eq!(dcf77.get_call_bit(), None);
+        assert_eq!(dcf77.get_bit_20(), None);
+        assert_eq!(dcf77.before_first_edge, true);
+        assert_eq!(dcf77.get_spike_limit(), 20_000);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58)); // kept
+        dcf77.reset(true);
+        assert_eq!(dcf77.radio_datetime.get_minute(), None);
+        assert_eq!(dcf77.radio_datetime.get_year(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_to_json() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(
+            dcf77.to_json(),
+            "{\"time\":null,\"date\":null,\"dst\":null,\"valid\":false,\"quality\":0}"
+        );
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(
+            dcf77.to_json(),
+            "{\"time\":\"16:58\",\"date\":\"2022-10-22\",\"dst\":\"CEST\",\"valid\":true,\"quality\":100}"
+        );
+    }
+
+    #[test]
+    fn test_feed_log_line() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        let mut line = String::new();
+        for b in BIT_BUFFER {
+            line.push(if b { '1' } else { '0' });
+        }
+        line.push_str("_\n");
+        assert_eq!(dcf77.feed_log_line(&line), true);
+        assert_eq!(dcf77.second, 59);
+        assert_eq!(dcf77.new_minute, true);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.radio_datetime.get_day(), Some(22));
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.second, 0);
+    }
+    #[test]
+    fn test_feed_log_line_invalid() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.feed_log_line(""), false);
+        assert_eq!(dcf77.feed_log_line("0101x"), false);
+        assert_eq!(dcf77.feed_log_line(&"0".repeat(61)), false);
+        assert_eq!(dcf77.second, 0);
+        assert_eq!(dcf77.get_current_bit(), None);
+    }
+
+    #[test]
+    fn test_is_test_pattern() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.is_test_pattern(), false); // empty buffer
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        assert_eq!(dcf77.is_test_pattern(), false);
+        dcf77.bit_buffer[20] = Some(false);
+        for b in 21..=58 {
+            dcf77.bit_buffer[b] = Some(true);
+        }
+        assert_eq!(dcf77.is_test_pattern(), true);
+        dcf77.bit_buffer[40] = None;
+        assert_eq!(dcf77.is_test_pattern(), false);
+    }
+
+    #[test]
+    fn test_new_edge_backwards() {
+        const EDGE_BUFFER: [(bool, u32); 4] = [
+            (!false, 366_097_734),
+            (!true, 366_879_141),
+            (!false, 366_879_041), // 100 us backwards
+            (!false, 366_993_436), // 114_295 us
+        ];
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.handle_new_edge(EDGE_BUFFER[0].0, EDGE_BUFFER[0].1);
+        assert_eq!(dcf77.get_last_edge_rejected(), false);
+        dcf77.handle_new_edge(EDGE_BUFFER[1].0, EDGE_BUFFER[1].1);
+        assert_eq!(dcf77.get_last_edge_rejected(), false);
+        assert_eq!(dcf77.t0, EDGE_BUFFER[1].1);
+        assert_eq!(dcf77.new_second, true);
+
+        dcf77.handle_new_edge(EDGE_BUFFER[2].0, EDGE_BUFFER[2].1);
+        assert_eq!(dcf77.get_last_edge_rejected(), true);
+        assert_eq!(dcf77.t0, EDGE_BUFFER[1].1); // unaltered
+        assert_eq!(dcf77.new_second, true);
+        assert_eq!(dcf77.new_minute, false);
+        assert_eq!(dcf77.get_current_bit(), None);
+
+        dcf77.handle_new_edge(EDGE_BUFFER[3].0, EDGE_BUFFER[3].1);
+        assert_eq!(dcf77.get_last_edge_rejected(), false);
+        assert_eq!(dcf77.t0, EDGE_BUFFER[3].1);
+        assert_eq!(dcf77.new_second, false);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+    }
+    #[test]
+    fn test_new_edge_wraparound() {
+        const EDGE_BUFFER: [(bool, u32); 3] = [
+            (!false, 4_294_000_000),
+            (!true, 4_294_800_000),
+            (!false, 137_704), // 305_000 us
+        ];
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        for edge in EDGE_BUFFER {
+            dcf77.handle_new_edge(edge.0, edge.1);
+            assert_eq!(dcf77.get_last_edge_rejected(), false);
+            assert_eq!(dcf77.t0, edge.1);
+        }
+    }
+
+    #[test]
+    fn test_uptime_minutes() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.uptime_minutes(), 0);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        dcf77.force_new_minute();
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.uptime_minutes(), 1);
+        // regular seconds do not count
+        dcf77.set_current_bit(Some(false));
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.uptime_minutes(), 1);
+        // incomplete minutes which cannot be decoded
+        for _ in 0..3 {
+            dcf77.decode_time(false);
+            dcf77.force_new_minute();
+            assert_eq!(dcf77.increase_second(), true);
+        }
+        assert_eq!(dcf77.uptime_minutes(), 4);
+        dcf77.reset(false);
+        assert_eq!(dcf77.uptime_minutes(), 4);
+        dcf77.reset(true);
+        assert_eq!(dcf77.uptime_minutes(), 0);
+    }
+
+    #[test]
+    fn test_order_violation_log_file() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.set_validate_order(true);
+        dcf77.set_current_bit(Some(false));
+        dcf77.decode_time(false); // correct, before increase_second()
+        assert_eq!(dcf77.get_order_violation(), false);
+        dcf77.increase_second();
+        dcf77.decode_time(false); // wrong, after increase_second()
+        assert_eq!(dcf77.get_order_violation(), true);
+        dcf77.set_current_bit(Some(false));
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_order_violation(), true); // sticky
+        dcf77.set_validate_order(true);
+        assert_eq!(dcf77.get_order_violation(), false);
+    }
+    #[test]
+    fn test_order_violation_live() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.set_validate_order(true);
+        dcf77.handle_new_edge(true, 1_000_000);
+        dcf77.increase_second();
+        dcf77.decode_time(false); // correct, after increase_second()
+        assert_eq!(dcf77.get_order_violation(), false);
+        dcf77.increase_second();
+        dcf77.handle_new_edge(false, 1_100_000);
+        dcf77.decode_time(false); // correct, edges do not matter
+        assert_eq!(dcf77.get_order_violation(), false);
+        dcf77.handle_new_edge(false, 2_000_000);
+        dcf77.decode_time(false); // wrong, before increase_second()
+        assert_eq!(dcf77.get_order_violation(), true);
+        dcf77.reset(false);
+        assert_eq!(dcf77.get_order_violation(), false);
+        assert_eq!(dcf77.get_validate_order(), true);
+    }
+    #[test]
+    fn test_order_violation_disabled() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_validate_order(), false);
+        dcf77.increase_second();
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_order_violation(), false);
+    }
+
+    #[test]
+    fn test_second_phase_error() {
+        const EDGE_BUFFER: [(bool, u32); 7] = [
+            (!false, 366_097_734),
+            (!true, 366_879_141),
+            (!false, 366_993_436), // 114_295 us
+            (!true, 367_879_221),  // 1_000_080 us second
+            (!false, 368_096_452), // 217_231 us
+            (!true, 369_878_721),  // 1_999_500 us second, new minute
+            (!true, 372_500_000),  // passive runaway
+        ];
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        for edge in &EDGE_BUFFER[0..=2] {
+            dcf77.handle_new_edge(edge.0, edge.1);
+            assert_eq!(dcf77.get_second_phase_error(), None);
+        }
+        dcf77.handle_new_edge(EDGE_BUFFER[3].0, EDGE_BUFFER[3].1);
+        assert_eq!(dcf77.new_second, true);
+        assert_eq!(dcf77.get_second_phase_error(), Some(80));
+        dcf77.handle_new_edge(EDGE_BUFFER[4].0, EDGE_BUFFER[4].1);
+        assert_eq!(dcf77.get_second_phase_error(), Some(80)); // unaltered
+        dcf77.handle_new_edge(EDGE_BUFFER[5].0, EDGE_BUFFER[5].1);
+        assert_eq!(dcf77.new_minute, true);
+        assert_eq!(dcf77.get_second_phase_error(), Some(-500));
+        dcf77.handle_new_edge(EDGE_BUFFER[6].0, EDGE_BUFFER[6].1);
+        assert_eq!(dcf77.t_second, None);
+        assert_eq!(dcf77.get_second_phase_error(), Some(-500)); // last measured value
+    }
+
+    #[test]
+    fn test_get_unix_time() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_unix_time(), None);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        // 2022-10-22 16:58 CEST is 14:58 UTC
+        assert_eq!(dcf77.get_unix_time(), Some(1_666_450_680));
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_system_time() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.to_system_time(), None);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(
+            dcf77
+                .to_system_time()
+                .unwrap()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            1_666_450_680
+        );
+    }
+
+    #[test]
+    fn test_increase_second_missed_minute_marker() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.second = 58;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.second, 59);
+        // no new minute arrives
+        dcf77.bit_buffer[59] = Some(false);
+        assert_eq!(dcf77.increase_second(), false);
+        assert_eq!(dcf77.second, 0);
+        assert_eq!(dcf77.get_desync_detected(), true);
+        // the complete minute can still be decoded
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.first_minute, false);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        // still desynchronized during the next minute
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.get_desync_detected(), true);
+        // until the end-of-minute marker arrives
+        dcf77.new_minute = true;
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.second, 0);
+        assert_eq!(dcf77.get_desync_detected(), false);
+    }
+
+    /// Edges for a whole minute of `BIT_BUFFER`, followed by the end-of-minute marker.
+    fn minute_edges(t: u32) -> [(bool, u32); 2 * 59 + 2] {
+        let mut edges = [(false, 0); 2 * 59 + 2];
+        for b in 0..=58 {
+            let t_second = t + b as u32 * 1_000_000;
+            edges[2 * b] = (!true, t_second);
+            edges[2 * b + 1] = (
+                !false,
+                t_second + if BIT_BUFFER[b] { 200_000 } else { 100_000 },
+            );
+        }
+        edges[2 * 59] = (!true, t + 60_000_000);
+        edges[2 * 59 + 1] = (!false, t + 60_100_000); // bit 0 of the next minute
+        edges
+    }
+
+    #[test]
+    fn test_step_live() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        let edges = minute_edges(1_000_000);
+        for (i, edge) in edges.iter().enumerate() {
+            let result = dcf77.step(StepInput::Edge(edge.0, edge.1), false);
+            assert_eq!(result.minute_completed, i == 2 * 59);
+            // each even edge starts a new second, the end-of-minute marker wraps to 0
+            let second = if i < 2 * 59 { (i / 2) as u8 } else { 0 };
+            assert_eq!(result.second, second);
+            assert_eq!(dcf77.get_second(), second);
+        }
+        assert_eq!(dcf77.get_old_second(), 59);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+        assert_eq!(dcf77.first_minute, false);
+        assert_eq!(dcf77.get_parities(), [Some(false); 3]);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.radio_datetime.get_weekday(), Some(6));
+        assert_eq!(dcf77.radio_datetime.get_day(), Some(22));
+        assert_eq!(dcf77.radio_datetime.get_month(), Some(10));
+        assert_eq!(dcf77.radio_datetime.get_year(), Some(22));
+    }
+    #[test]
+    fn test_step_log_file() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        let mut manual = DCF77Utils::new(DecodeType::LogFile);
+        for b in 0..=59 {
+            let bit = if b < 59 { Some(BIT_BUFFER[b]) } else { None };
+            let result = dcf77.step(StepInput::Bit(bit), true);
+            manual.set_current_bit(bit);
+            if b == 59 {
+                manual.decode_time(true);
+                manual.force_new_minute();
+            }
+            manual.increase_second();
+            assert_eq!(result.minute_completed, b == 59);
+            assert_eq!(result.second, manual.get_second());
+        }
+        assert_eq!(dcf77.get_second(), 0);
+        assert_eq!(dcf77.first_minute, false);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.radio_datetime.get_weekday(), Some(6));
+        assert_eq!(dcf77.radio_datetime.get_day(), Some(22));
+        assert_eq!(dcf77.radio_datetime.get_month(), Some(10));
+        assert_eq!(dcf77.radio_datetime.get_year(), Some(22));
+        assert_eq!(dcf77.get_snapshot(), manual.get_snapshot());
+    }
+    #[test]
+    fn test_step_wrong_input() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        let result = dcf77.step(StepInput::Edge(true, 1_000_000), false);
+        assert_eq!(result.minute_completed, false);
+        assert_eq!(result.second, 0);
+        assert_eq!(dcf77.before_first_edge, true);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_to_chrono() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.to_naive_date_time(), None);
+        assert_eq!(dcf77.to_date_time_utc(), None);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(
+            dcf77.to_naive_date_time(),
+            chrono::NaiveDate::from_ymd_opt(2022, 10, 22)
+                .unwrap()
+                .and_hms_opt(16, 58, 0)
+        );
+        assert_eq!(
+            dcf77.to_date_time_utc().map(|dt| dt.timestamp()),
+            Some(1_666_450_680)
+        );
+        // Sunday instead of Saturday
+        dcf77.radio_datetime.set_weekday(Some(7), true, false);
+        assert_eq!(dcf77.to_naive_date_time(), None);
+        assert_eq!(dcf77.to_date_time_utc(), None);
+    }
+
+    /// Feed the signal described by `edges` as samples taken every 10 ms from `t_start`
+    /// until `t_stop` to both `handle_sample()` and `handle_new_edge()`.
+    fn compare_samples(edges: &[(bool, u32)], t_start: u32, t_stop: u32) -> DCF77Utils {
+        let mut dcf77_edges = DCF77Utils::new(DecodeType::Live);
+        for edge in edges {
+            dcf77_edges.handle_new_edge(edge.0, edge.1);
+        }
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        let mut is_active = edges[0].0; // level before the first edge
+        let mut e = 0;
+        for t in (t_start..t_stop).step_by(10_000) {
+            while e < edges.len() && edges[e].1 <= t {
+                is_active = !edges[e].0;
+                e += 1;
+            }
+            dcf77.handle_sample(is_active, t);
+        }
+        assert_eq!(dcf77.get_current_bit(), dcf77_edges.get_current_bit());
+        assert_eq!(dcf77.new_second, dcf77_edges.new_second);
+        assert_eq!(dcf77.new_minute, dcf77_edges.new_minute);
+        dcf77
+    }
+
+    #[test]
+    fn test_handle_sample_bit_0() {
+        const EDGE_BUFFER: [(bool, u32); 4] = [
+            (!false, 366_097_734),
+            (!true, 366_879_141),
+            (!false, 366_993_436), // 114_295 us
+            (!true, 367_879_221),
+        ];
+        let dcf77 = compare_samples(&EDGE_BUFFER, 366_000_000, 368_000_000);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+        assert_eq!(dcf77.new_second, true);
+        assert_eq!(dcf77.t0, 367_880_000);
+    }
+    #[test]
+    fn test_handle_sample_bit_1() {
+        const EDGE_BUFFER: [(bool, u32); 4] = [
+            (!false, 361_997_291),
+            (!true, 362_879_580),
+            (!false, 363_096_452), // 216_872 us
+            (!true, 363_879_672),
+        ];
+        let dcf77 = compare_samples(&EDGE_BUFFER, 361_900_000, 364_000_000);
+        assert_eq!(dcf77.get_current_bit(), Some(true));
+        assert_eq!(dcf77.new_second, true);
+        assert_eq!(dcf77.t0, 363_880_000);
+    }
+    #[test]
+    fn test_handle_sample_spike() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.handle_sample(false, 1_000_000);
+        dcf77.handle_sample(true, 1_010_000); // start of the second
+        assert_eq!(dcf77.before_first_edge, true); // not yet stable
+        dcf77.handle_sample(true, 1_040_000);
+        assert_eq!(dcf77.before_first_edge, false);
+        assert_eq!(dcf77.t0, 1_010_000);
+        dcf77.handle_sample(false, 1_150_000); // bit 0
+        dcf77.handle_sample(false, 1_180_000);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+        assert_eq!(dcf77.t0, 1_150_000);
+        dcf77.handle_sample(true, 1_500_000); // spike
+        dcf77.handle_sample(true, 1_510_000);
+        dcf77.handle_sample(false, 1_520_000);
+        dcf77.handle_sample(false, 1_530_000);
+        assert_eq!(dcf77.t0, 1_150_000);
+        assert_eq!(dcf77.new_second, false);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+    }
+
+    #[test]
+    fn test_get_weekday_enum() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_weekday_enum(), None);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_weekday_enum(), Some(Weekday::Saturday));
+        // Sunday, fix the date parity:
+        dcf77.bit_buffer[42] = Some(true);
+        dcf77.bit_buffer[58] = Some(false);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_weekday_enum(), Some(Weekday::Sunday));
+    }
+
+    #[test]
+    fn test_date_parity_hint_ok() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_date_parity_hint(), None);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_date_parity_hint(), None);
+    }
+    #[test]
+    fn test_date_parity_hint_bad_bits() {
+        for (bad_bit, hint) in [
+            (40, DateParityHint::Day),     // day 32
+            (47, DateParityHint::Month),   // month 14
+            (57, DateParityHint::Year),    // year 2102
+            (58, DateParityHint::Parity),  // just the parity
+            (36, DateParityHint::Unknown), // Sunday 2022-10-23 is also valid
+            (43, DateParityHint::Unknown), // Thursday 2022-10-20 is also valid
+        ] {
+            let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+            dcf77.second = 59;
+            for b in 0..=58 {
+                dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+            }
+            dcf77.bit_buffer[bad_bit] = Some(!dcf77.bit_buffer[bad_bit].unwrap());
+            dcf77.decode_time(false);
+            assert_eq!(dcf77.parity_3, Some(true));
+            assert_eq!(dcf77.get_date_parity_hint(), Some(hint));
+        }
+    }
+    #[test]
+    fn continue_date_parity_hint_bad_bits() {
+        for (bad_bit, hint) in [
+            (36, DateParityHint::Day),
+            (43, DateParityHint::Weekday),
+            (45, DateParityHint::Month),
+            (53, DateParityHint::Year),
+        ] {
+            let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+            dcf77.second = 59;
+            for b in 0..=58 {
+                dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+            }
+            dcf77.decode_time(false);
+            // the expected date is known now
+            dcf77.bit_buffer[bad_bit] = Some(!dcf77.bit_buffer[bad_bit].unwrap());
+            dcf77.decode_time(false);
+            assert_eq!(dcf77.parity_3, Some(true));
+            assert_eq!(dcf77.get_date_parity_hint(), Some(hint));
+        }
+    }
+    #[test]
+    fn test_date_parity_hint_broken_bit() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.bit_buffer[39] = None;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.parity_3, None);
+        assert_eq!(dcf77.get_date_parity_hint(), None);
+    }
+
+    #[test]
+    fn test_decode_edges() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        let first = minute_edges(1_000_000);
+        let second = minute_edges(61_000_000);
+        let edges = first[..2 * 59].iter().chain(second.iter()).copied();
+        let minutes: Vec<RadioDateTimeUtils> = dcf77.decode_edges(edges, true).collect();
+        assert_eq!(minutes.len(), 2);
+        for minute in &minutes {
+            assert_eq!(minute.get_minute(), Some(58)); // same bits twice
+            assert_eq!(minute.get_hour(), Some(16));
+            assert_eq!(minute.get_weekday(), Some(6));
+            assert_eq!(minute.get_day(), Some(22));
+            assert_eq!(minute.get_month(), Some(10));
+            assert_eq!(minute.get_year(), Some(22));
+        }
+        assert_eq!(minutes[0].get_jump_minute(), false);
+        assert_eq!(minutes[1].get_jump_minute(), true);
+        assert_eq!(dcf77.get_second(), 0);
+    }
+    #[test]
+    fn test_decode_edges_broken() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        let mut edges = minute_edges(1_000_000);
+        edges[2 * 26 + 1].1 += 100_000; // flip bit 26 to 1
+        assert_eq!(dcf77.decode_edges(edges, true).count(), 0);
+        assert_eq!(dcf77.get_parity_1(), Some(true));
+    }
+    #[test]
+    fn test_decode_edges_good_then_broken() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        let first = minute_edges(1_000_000);
+        let mut second = minute_edges(61_000_000);
+        second[2 * 26 + 1].1 += 100_000; // flip bit 26 to 1
+        let edges = first[..2 * 59].iter().chain(second.iter()).copied();
+        let minutes: Vec<RadioDateTimeUtils> = dcf77.decode_edges(edges, true).collect();
+        assert_eq!(minutes.len(), 1);
+        assert_eq!(minutes[0].get_minute(), Some(58));
+        assert_eq!(dcf77.get_parity_1(), Some(true));
+        assert_eq!(dcf77.get_first_minute(), false);
+        assert_eq!(dcf77.get_minutes_since_decode(), 1);
+    }
+
+    #[test]
+    fn test_is_leap_second_now() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        // nothing known yet
+        assert_eq!(dcf77.is_leap_second_now(), false);
+        // minute 59 with a leap second announcement:
+        dcf77.bit_buffer[19] = Some(true);
+        dcf77.bit_buffer[21] = Some(true);
+        dcf77.bit_buffer[28] = Some(false);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_next_minute_length(), 61);
+        assert_eq!(dcf77.is_leap_second_now(), false); // end-of-minute marker of this minute
+        dcf77.force_new_minute();
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.second, 0);
+        while dcf77.second < 59 {
+            assert_eq!(dcf77.is_leap_second_now(), false);
+            dcf77.set_current_bit(Some(false));
+            assert_eq!(dcf77.increase_second(), true);
+        }
+        assert_eq!(dcf77.is_leap_second_now(), true);
+        dcf77.set_current_bit(Some(false));
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.second, 60); // end-of-minute marker
+        assert_eq!(dcf77.is_leap_second_now(), false);
+    }
+
+    #[test]
+    fn test_minutes_since_decode() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_minutes_since_decode(), 0);
+        dcf77.second = 42;
+        dcf77.decode_time(true); // incomplete minute
+        assert_eq!(dcf77.get_minutes_since_decode(), 1);
+        assert_eq!(dcf77.get_first_minute(), true); // never synchronized
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(true);
+        assert_eq!(dcf77.get_minutes_since_decode(), 0);
+        dcf77.bit_buffer[26] = Some(!dcf77.bit_buffer[26].unwrap());
+        dcf77.decode_time(true);
+        dcf77.decode_time(true);
+        assert_eq!(dcf77.get_minutes_since_decode(), 2);
+        assert_eq!(dcf77.get_first_minute(), false); // stale
+        dcf77.reset(false);
+        assert_eq!(dcf77.get_minutes_since_decode(), 0);
+    }
+
+    #[test]
+    fn test_dst_bits_valid() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_dst_bits_valid(), None);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_dst_bits_valid(), Some(true));
+        dcf77.bit_buffer[18] = Some(true); // both set
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_dst_bits_valid(), Some(false));
+        dcf77.bit_buffer[17] = Some(false); // both clear
+        dcf77.bit_buffer[18] = Some(false);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_dst_bits_valid(), Some(false));
+        dcf77.bit_buffer[17] = None;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_dst_bits_valid(), None);
+    }
+
+    /// Decode a good minute, followed by a broken minute with a plausible but wrong hour.
+    fn decode_good_and_broken_minute(hold_last_good: bool) -> DCF77Utils {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.set_hold_last_good(hold_last_good);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_minutes_since_decode(), 0);
+        // hour 17 with a good parity, but a broken bit 20:
+        dcf77.bit_buffer[29] = Some(true);
+        dcf77.bit_buffer[35] = Some(false);
+        dcf77.bit_buffer[20] = Some(false);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.parity_2, Some(false));
+        assert_eq!(dcf77.get_minutes_since_decode(), 1);
+        dcf77
+    }
+
+    #[test]
+    fn continue_decode_time_hold_last_good() {
+        let dcf77 = decode_good_and_broken_minute(true);
+        assert_eq!(dcf77.get_hold_last_good(), true);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(59)); // advanced
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16)); // kept
+        assert_eq!(dcf77.radio_datetime.get_weekday(), Some(6));
+        assert_eq!(dcf77.radio_datetime.get_day(), Some(22));
+        assert_eq!(dcf77.radio_datetime.get_month(), Some(10));
+        assert_eq!(dcf77.radio_datetime.get_year(), Some(22));
+        assert_eq!(
+            dcf77.radio_datetime.get_dst(),
+            Some(radio_datetime_utils::DST_SUMMER)
+        );
+        assert_eq!(dcf77.radio_datetime.get_jump_minute(), false);
+        assert_eq!(dcf77.radio_datetime.get_jump_hour(), false);
+    }
+    #[test]
+    fn continue_decode_time_no_hold_last_good() {
+        let dcf77 = decode_good_and_broken_minute(false);
+        assert_eq!(dcf77.get_hold_last_good(), false);
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(17)); // overwritten
+        assert_eq!(dcf77.radio_datetime.get_jump_hour(), true);
+    }
+
+    #[test]
+    fn test_observed_minute_length() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_observed_minute_length(), None);
+        // start somewhere in the middle of a minute
+        dcf77.second = 23;
+        dcf77.force_new_minute();
+        assert_eq!(dcf77.increase_second(), true);
+        assert_eq!(dcf77.get_observed_minute_length(), None);
+        // regular, one extra edge (overflow), two missed edges, two extra edges (overflow)
+        for seconds in [59, 60, 57, 61] {
+            for _ in 0..seconds {
+                dcf77.set_current_bit(Some(false));
+                dcf77.increase_second();
+            }
+            assert_eq!(dcf77.get_desync_detected(), seconds >= 60);
+            dcf77.force_new_minute();
+            assert_eq!(dcf77.increase_second(), true);
+            assert_eq!(dcf77.get_observed_minute_length(), Some(seconds + 1));
+            assert_eq!(dcf77.get_desync_detected(), false);
+        }
+    }
+    #[test]
+    fn test_observed_minute_length_step_live() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        let first = minute_edges(1_000_000);
+        let second = minute_edges(61_000_000);
+        for edge in first[..2 * 59].iter().chain(second.iter()) {
+            dcf77.step(StepInput::Edge(edge.0, edge.1), false);
+        }
+        assert_eq!(dcf77.get_observed_minute_length(), Some(60));
+    }
+
+    #[test]
+    fn test_display_decoded_time() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(
+            format!("{}", dcf77.get_decoded_time()),
+            "---------- --:-- -- --"
+        );
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(
+            format!("{}", dcf77.get_decoded_time()),
+            "2022-10-22 16:58 CEST Sat"
+        );
+    }
+    #[test]
+    fn test_debug() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.bit_buffer[26] = Some(!dcf77.bit_buffer[26].unwrap());
+        dcf77.decode_time(false);
+        let debug = format!("{:?}", dcf77);
+        assert!(debug.starts_with("DCF77Utils { decoded_time: DecodedTime { year: Some(22), "));
+        assert!(debug.contains("parity_1: Some(true), parity_2: Some(false), "));
+        assert!(debug.contains("call_bit: Some(true), "));
+        assert!(debug.ends_with(", .. }"));
+    }
+
+    #[test]
+    fn test_call_bit_history() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_call_bit_history().is_empty(), true);
+        assert_eq!(
+            dcf77.get_call_bit_active_recently(CALL_BIT_HISTORY_SIZE),
+            false
+        );
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false); // call bit set
+        dcf77.bit_buffer[15] = Some(false);
+        dcf77.decode_time(false);
+        dcf77.second = 42;
+        dcf77.decode_time(false); // incomplete minute
+        assert_eq!(
+            dcf77.get_call_bit_history(),
+            &[Some(true), Some(false), None]
+        );
+        assert_eq!(dcf77.get_call_bit_active_recently(2), false);
+        assert_eq!(dcf77.get_call_bit_active_recently(3), true);
+        assert_eq!(dcf77.get_call_bit_active_recently(100), true);
+        dcf77.second = 59;
+        for _ in 0..CALL_BIT_HISTORY_SIZE {
+            dcf77.decode_time(false);
+        }
+        assert_eq!(
+            dcf77.get_call_bit_history(),
+            &[Some(false); CALL_BIT_HISTORY_SIZE]
+        );
+        assert_eq!(
+            dcf77.get_call_bit_active_recently(CALL_BIT_HISTORY_SIZE),
+            false
+        );
+        dcf77.reset(false);
+        assert_eq!(dcf77.get_call_bit_history().len(), CALL_BIT_HISTORY_SIZE);
+        dcf77.reset(true);
+        assert_eq!(dcf77.get_call_bit_history().is_empty(), true);
+    }
+
+    #[test]
+    fn test_set_minute_bits() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.set_minute_bits(&[None; 58]), Err(()));
+        assert_eq!(dcf77.set_minute_bits(&[None; 62]), Err(()));
+        assert_eq!(dcf77.get_current_bit(), None);
+        let mut bits = [None; 60];
+        for b in 0..=58 {
+            bits[b] = Some(BIT_BUFFER[b]);
+        }
+        assert_eq!(dcf77.set_minute_bits(&bits), Ok(()));
+        assert_eq!(dcf77.get_second(), 59);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.get_first_minute(), false);
+        assert_eq!(dcf77.set_minute_bits(&[None; 61]), Ok(()));
+        assert_eq!(dcf77.get_second(), 60);
+        assert_eq!(dcf77.get_current_bit(), None);
+    }
+    #[test]
+    fn test_set_minute_bits_implied_marker() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        let mut bits = [None; 59];
+        for b in 0..=58 {
+            bits[b] = Some(BIT_BUFFER[b]);
+        }
+        assert_eq!(dcf77.set_minute_bits(&bits), Ok(()));
+        assert_eq!(dcf77.get_second(), 59);
+        assert_eq!(dcf77.get_current_bit(), None);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Ok);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.first_minute, false);
+    }
+
+    #[test]
+    fn test_signal_lost() {
+        const EDGE_BUFFER: [(bool, u32); 5] = [
+            (!true, 100_000_000),
+            (!false, 100_100_000),
+            (!true, 104_000_000), // 3_900_000 us passive, runaway
+            (!false, 104_200_000),
+            (!true, 105_000_000), // regular second
+        ];
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.handle_new_edge(EDGE_BUFFER[0].0, EDGE_BUFFER[0].1);
+        dcf77.handle_new_edge(EDGE_BUFFER[1].0, EDGE_BUFFER[1].1);
+        assert_eq!(dcf77.get_signal_lost(), false);
+        dcf77.handle_new_edge(EDGE_BUFFER[2].0, EDGE_BUFFER[2].1);
+        assert_eq!(dcf77.get_signal_lost(), true);
+        assert_eq!(dcf77.get_current_bit(), None);
+        dcf77.handle_new_edge(EDGE_BUFFER[3].0, EDGE_BUFFER[3].1);
+        assert_eq!(dcf77.get_signal_lost(), true);
+        dcf77.handle_new_edge(EDGE_BUFFER[4].0, EDGE_BUFFER[4].1);
+        assert_eq!(dcf77.get_signal_lost(), false);
+        dcf77.handle_new_edge(!true, 110_000_000); // 5_000_000 us passive, runaway
+        assert_eq!(dcf77.get_signal_lost(), true);
+        dcf77.reset(false);
+        assert_eq!(dcf77.get_signal_lost(), false);
+    }
+
+    #[test]
+    fn test_set_decode_type() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        for edge in minute_edges(1_000_000) {
+            dcf77.step(StepInput::Edge(edge.0, edge.1), false);
+        }
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        dcf77.set_decode_type(DecodeType::LogFile);
+        assert_eq!(dcf77.get_decode_type(), DecodeType::LogFile);
+        let mut minute_completed = false;
+        for b in 0..=59 {
+            let bit = match b {
+                21 => Some(true),  // minute 59
+                28 => Some(false), // minute parity
+                59 => None,
+                _ => Some(BIT_BUFFER[b]),
+            };
+            minute_completed = dcf77.step(StepInput::Bit(bit), false).minute_completed;
+        }
+        assert_eq!(minute_completed, true);
+        assert_eq!(dcf77.get_second(), 0);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(59));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.radio_datetime.get_jump_minute(), false);
+        assert_eq!(dcf77.get_minutes_since_decode(), 0);
+    }
+
+    #[test]
+    fn test_decode_time_invalid_bcd() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        // minute 0x1a, with valid parity
+        for (b, v) in [false, true, false, true, true, false, false, true]
+            .iter()
+            .enumerate()
+        {
+            dcf77.bit_buffer[21 + b] = Some(*v);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_parity_1(), Some(false));
+        assert_eq!(dcf77.radio_datetime.get_minute(), None);
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.first_minute, true);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::InvalidValue);
+        assert_eq!(dcf77.get_minutes_since_decode(), 1);
+
+        // a regular minute, followed by one with an invalid minute
+        for b in 21..=28 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Ok);
+        assert_eq!(dcf77.first_minute, false);
+        dcf77.bit_buffer[22] = Some(true);
+        dcf77.bit_buffer[23] = Some(true); // minute 0x5e, same parity
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::InvalidValue);
+        assert_eq!(dcf77.get_minutes_since_decode(), 1);
+    }
+
+    #[test]
+    fn test_adaptive_spike_limit() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        assert_eq!(dcf77.get_adaptive_spike_limit(), false);
+        dcf77.set_adaptive_spike_limit(true);
+        assert_eq!(dcf77.get_adaptive_spike_limit(), true);
+        assert_eq!(dcf77.get_spike_limit(), SPIKE_LIMIT);
+        // noisy antenna, the 0 bits bounce twice with 40_000 us, longer than the spike limit
+        for s in 0..60 {
+            let t = 1_000_000 + s * 1_000_000;
+            dcf77.handle_new_edge(!true, t);
+            dcf77.handle_new_edge(!false, t + 100_000);
+            dcf77.handle_new_edge(!true, t + 140_000);
+            dcf77.handle_new_edge(!false, t + 180_000);
+            assert_eq!(dcf77.get_spike_limit() <= ADAPTIVE_SPIKE_LIMIT_MAX, true);
+        }
+        // converged to just above the length of the bounces
+        assert_eq!(dcf77.get_spike_limit(), 40_000 + SPIKE_LIMIT_STEP);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+        // quiet antenna, the spike limit is lowered again
+        for s in 60..120 {
+            let t = 1_000_000 + s * 1_000_000;
+            dcf77.handle_new_edge(!true, t);
+            if s < 70 {
+                // kept while the rate of rejected spikes decays
+                assert_eq!(dcf77.get_spike_limit(), 40_000 + SPIKE_LIMIT_STEP);
+            }
+            dcf77.handle_new_edge(!false, t + 100_000);
+        }
+        assert_eq!(dcf77.get_spike_limit() < SPIKE_LIMIT, true);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+    }
+
+    #[test]
+    fn test_adaptive_spike_limit_capped() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.set_adaptive_spike_limit(true);
+        // very noisy antenna, the active part is split into pulses of 110_000 to 130_000 us
+        // and 110_000 us by a short dip
+        for s in 0..60 {
+            let t = 1_000_000 + s * 1_000_000;
+            let a = 110_000 + (s % 3) * 10_000;
+            dcf77.handle_new_edge(!true, t);
+            dcf77.handle_new_edge(!false, t + a);
+            dcf77.handle_new_edge(!true, t + a + 5_000);
+            dcf77.handle_new_edge(!false, t + a + 115_000);
+            assert_eq!(dcf77.get_spike_limit() <= ADAPTIVE_SPIKE_LIMIT_MAX, true);
+            assert_eq!(dcf77.get_current_bit(), Some(false));
+        }
+        assert_eq!(dcf77.get_spike_limit(), ADAPTIVE_SPIKE_LIMIT_MAX);
+        // a clean 0 bit is still accepted
+        dcf77.bit_buffer[dcf77.second as usize] = None;
+        dcf77.handle_new_edge(!true, 61_000_000);
+        dcf77.handle_new_edge(!false, 61_100_000);
+        assert_eq!(dcf77.get_spike_limit(), ADAPTIVE_SPIKE_LIMIT_MAX);
+        assert_eq!(dcf77.get_current_bit(), Some(false));
+    }
+
+    #[test]
+    fn test_get_frame_bits() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.get_frame_bits(), (0, 0));
+        let mut values = 0;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+            values |= (BIT_BUFFER[b] as u64) << b;
+        }
+        dcf77.bit_buffer[30] = None;
+        values &= !(1 << 30);
+        assert_eq!(dcf77.get_frame_bits(), (values, 0x07ff_ffff_bfff_ffff));
+
+        // unpack into a fresh decoder
+        let (values, mask) = dcf77.get_frame_bits();
+        let mut bits = [None; 60];
+        for (b, bit) in bits.iter_mut().enumerate() {
+            if mask & (1 << b) != 0 {
+                *bit = Some(values & (1 << b) != 0);
+            }
+        }
+        let mut unpacked = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(unpacked.set_minute_bits(&bits), Ok(()));
+        assert_eq!(unpacked.get_frame_bits(), (values, mask));
+    }
+
+    #[test]
+    fn test_last_decode_result() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::IncompleteMinute);
+        dcf77.second = 59;
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Ok);
+        dcf77.bit_buffer[18] = Some(true);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::DstInvalid);
+        dcf77.bit_buffer[20] = None;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Bit20Wrong);
+        dcf77.bit_buffer[0] = Some(true);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Bit0Wrong);
+        dcf77.bit_buffer[40] = Some(!BIT_BUFFER[40]);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::ParityDate);
+        dcf77.bit_buffer[30] = None;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::ParityHour);
+        dcf77.bit_buffer[22] = Some(!BIT_BUFFER[22]);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::ParityMinute);
+        dcf77.second = 42;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::IncompleteMinute);
+    }
+
+    #[test]
+    fn test_decode_time_partial_minute() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::LogFile);
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        dcf77.second = 58; // minute ended one second early
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::IncompleteMinute);
+        assert_eq!(dcf77.radio_datetime.get_minute(), None);
+
+        dcf77.set_decode_partial_minutes(true);
+        assert_eq!(dcf77.get_decode_partial_minutes(), true);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Partial);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(58));
+        assert_eq!(dcf77.radio_datetime.get_hour(), Some(16));
+        assert_eq!(dcf77.get_minutes_since_decode(), 0);
+        assert_eq!(dcf77.first_minute, true); // never from a partial minute
+
+        // a date/time bit is missing
+        dcf77.bit_buffer[40] = None;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::IncompleteMinute);
+        dcf77.bit_buffer[40] = Some(BIT_BUFFER[40]);
+
+        // bits 51 to 58 are left over from the previous minute
+        dcf77.second = 50;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::IncompleteMinute);
+
+        // a partial minute after a regular one
+        dcf77.second = 59;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Ok);
+        assert_eq!(dcf77.first_minute, false);
+        dcf77.bit_buffer[21] = Some(true); // minute 59
+        dcf77.bit_buffer[28] = Some(false); // minute parity
+        dcf77.second = 58;
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.last_decode_result(), DecodeOutcome::Partial);
+        assert_eq!(dcf77.radio_datetime.get_minute(), Some(59));
+        assert_eq!(dcf77.radio_datetime.get_jump_minute(), false);
+        assert_eq!(dcf77.first_minute, false);
+    }
+
+    #[test]
+    fn test_is_leap_second_now_live() {
+        let mut dcf77 = DCF77Utils::new(DecodeType::Live);
+        dcf77.second = 58; // unmodulated second 59 is not counted
+        for b in 0..=58 {
+            dcf77.bit_buffer[b] = Some(BIT_BUFFER[b]);
+        }
+        // minute 59 with a leap second announcement:
+        dcf77.bit_buffer[19] = Some(true);
+        dcf77.bit_buffer[21] = Some(true);
+        dcf77.bit_buffer[28] = Some(false);
+        dcf77.new_minute = true;
+        dcf77.second += 1;
+        assert_eq!(dcf77.increase_second(), true);
+        dcf77.decode_time(false);
+        assert_eq!(dcf77.get_next_minute_length(), 61);
+        dcf77.new_minute = false;
+        while dcf77.second < 59 {
+            assert_eq!(dcf77.is_leap_second_now(), false);
+            assert_eq!(dcf77.increase_second(), true);
+        }
+        assert_eq!(dcf77.is_leap_second_now(), true);
+    }
 }